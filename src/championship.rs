@@ -0,0 +1,252 @@
+use crate::client::Client;
+use crate::data_api::Championship;
+use crate::live::MatchStatus;
+use crate::shared::{Items, Response};
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize)]
+struct Championship_ {
+    id: String,
+    name: String,
+    description: Option<String>,
+    game: Option<String>,
+    region: Option<String>,
+    #[serde(rename = "organizerId")]
+    organizer_id: Option<String>,
+    status: Option<String>,
+    #[serde(rename = "type")]
+    kind: Option<String>,
+    slots: Option<u32>,
+    #[serde(rename = "currentSubscriptions")]
+    current_subscriptions: Option<u32>,
+    #[serde(rename = "totalRounds")]
+    total_rounds: Option<u32>,
+}
+
+impl From<Championship_> for Championship {
+    fn from(c: Championship_) -> Championship {
+        Championship {
+            faceit_url: Some(format!("https://www.faceit.com/en/championship/{}", c.id)),
+            id: c.id,
+            name: c.name,
+            description: c.description,
+            game_id: c.game,
+            region: c.region,
+            organizer_id: c.organizer_id,
+            status: c.status,
+            kind: c.kind,
+            slots: c.slots,
+            current_subscriptions: c.current_subscriptions,
+            total_rounds: c.total_rounds,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct BracketTeam {
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Deserialize)]
+struct Origin_ {
+    id: String,
+}
+
+#[derive(Deserialize)]
+struct BracketMatch_ {
+    id: String,
+    origin: Option<Origin_>,
+    round: u32,
+    position: u32,
+    state: String,
+    faction1: Option<BracketTeam>,
+    faction2: Option<BracketTeam>,
+    winner: Option<String>,
+}
+
+/// A match in the bracket of a championship.
+#[derive(Serialize, Debug)]
+pub struct BracketMatch {
+    pub id: String,
+    /// The room id once the match was created, see [`Client::room`].
+    pub room_id: Option<String>,
+    /// Starting at `1`.
+    pub round: u32,
+    /// Position within the round, starting at `1`.
+    pub position: u32,
+    pub status: MatchStatus,
+    pub faction_1: Option<BracketTeam>,
+    pub faction_2: Option<BracketTeam>,
+    /// Team id of the winner.
+    pub winner: Option<String>,
+}
+
+impl From<BracketMatch_> for BracketMatch {
+    fn from(m: BracketMatch_) -> BracketMatch {
+        BracketMatch {
+            id: m.id,
+            room_id: m.origin.map(|origin| origin.id),
+            round: m.round,
+            position: m.position,
+            status: MatchStatus::parse(&m.state),
+            faction_1: m.faction1,
+            faction_2: m.faction2,
+            winner: m.winner,
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+pub struct BracketRound {
+    pub round: u32,
+    /// Ordered by position.
+    pub matches: Vec<BracketMatch>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct Bracket {
+    pub championship_id: String,
+    /// Ordered by round, the last round is the final.
+    pub rounds: Vec<BracketRound>,
+}
+
+impl Bracket {
+    pub fn from_matches(championship_id: &str, matches: Vec<BracketMatch>) -> Bracket {
+        let mut rounds = BTreeMap::<u32, Vec<BracketMatch>>::new();
+        for m in matches {
+            rounds.entry(m.round).or_default().push(m);
+        }
+        let rounds = rounds.into_iter().map(|(round, mut matches)| {
+            matches.sort_by_key(|m| m.position);
+            BracketRound { round, matches }
+        });
+
+        Bracket {
+            championship_id: championship_id.to_string(),
+            rounds: rounds.collect(),
+        }
+    }
+
+    /// Team id of the winner of the final.
+    pub fn winner(&self) -> Option<&str> {
+        let last = self.rounds.last()?;
+        match last.matches.as_slice() {
+            [last] => last.winner.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+impl Client {
+    /// - `https://api.faceit.com/championships/v1/championship/{CHAMPIONSHIP_ID}`
+    pub async fn championship(&self, championship_id: &str) -> reqwest::Result<Championship> {
+        const PREFIX: &str = "https://api.faceit.com/championships/v1/championship";
+
+        let url = format!("{}/{}", PREFIX, championship_id);
+
+        let resp = self.get_json::<Response<Championship_>>(&url, &[]).await?;
+        Ok(resp.payload.into())
+    }
+
+    /// - `https://api.faceit.com/championships/v1/matches`
+    pub async fn championship_matches(
+        &self,
+        championship_id: &str,
+        offset: usize,
+        limit: usize,
+    ) -> reqwest::Result<Vec<BracketMatch>> {
+        const API: &str = "https://api.faceit.com/championships/v1/matches";
+
+        let (offset, limit) = (offset.to_string(), limit.to_string());
+        let query = [
+            ("championshipId", championship_id),
+            ("offset", &offset),
+            ("limit", &limit),
+        ];
+
+        let resp = self.get_json::<Response<Items<BracketMatch_>>>(API, &query);
+        let matches = resp.await?.payload.items.into_iter();
+        Ok(matches.map(BracketMatch::from).collect())
+    }
+
+    /// Fetches every match of the championship, until the api returns
+    /// an empty page, and groups them by round.
+    pub async fn championship_bracket(&self, championship_id: &str) -> reqwest::Result<Bracket> {
+        const PAGE_SIZE: usize = 100;
+
+        let mut matches = Vec::new();
+        loop {
+            let page = self
+                .championship_matches(championship_id, matches.len(), PAGE_SIZE)
+                .await?;
+            if page.is_empty() {
+                break;
+            }
+            matches.extend(page);
+        }
+        Ok(Bracket::from_matches(championship_id, matches))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bracket_match(round: u32, position: u32, winner: Option<&str>) -> BracketMatch {
+        let team = |id: &str| BracketTeam {
+            id: id.to_string(),
+            name: format!("team {}", id),
+        };
+        BracketMatch {
+            id: format!("{}-{}", round, position),
+            room_id: winner.map(|_| format!("1-room-{}-{}", round, position)),
+            round,
+            position,
+            status: match winner {
+                Some(_) => MatchStatus::Finished,
+                None => MatchStatus::Other("SCHEDULED".to_string()),
+            },
+            faction_1: Some(team("a")),
+            faction_2: Some(team("b")),
+            winner: winner.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn groups_rounds_in_order() {
+        let matches = vec![
+            bracket_match(2, 1, None),
+            bracket_match(1, 2, Some("c")),
+            bracket_match(1, 1, Some("a")),
+        ];
+        let bracket = Bracket::from_matches("c1", matches);
+
+        let rounds = bracket.rounds.iter().map(|r| r.round).collect::<Vec<_>>();
+        assert_eq!(rounds, [1, 2]);
+        let positions = bracket.rounds[0].matches.iter().map(|m| m.position);
+        assert_eq!(positions.collect::<Vec<_>>(), [1, 2]);
+        assert_eq!(bracket.winner(), None);
+    }
+
+    #[test]
+    fn winner_of_the_final() {
+        let matches = vec![
+            bracket_match(1, 1, Some("a")),
+            bracket_match(1, 2, Some("c")),
+            bracket_match(2, 1, Some("c")),
+        ];
+        assert_eq!(Bracket::from_matches("c1", matches).winner(), Some("c"));
+
+        // Without a single final there is no winner yet.
+        let matches = vec![
+            bracket_match(1, 1, Some("a")),
+            bracket_match(1, 2, Some("c")),
+        ];
+        assert_eq!(Bracket::from_matches("c1", matches).winner(), None);
+        assert_eq!(Bracket::from_matches("c1", Vec::new()).winner(), None);
+    }
+}
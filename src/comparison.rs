@@ -0,0 +1,178 @@
+use crate::client::Client;
+use crate::form::{Form, FormOptions};
+use crate::map_performance::{MapPerformance, Window};
+use crate::mapping::{MapStats, Mapping};
+use crate::matches::Matches;
+use crate::player_info::PlayerInfo;
+use crate::stats::Stats;
+
+use chrono::Local;
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+pub struct ComparedPlayer {
+    pub id: String,
+    pub nickname: String,
+    pub country: String,
+    pub elo: u16,
+    pub skill_level: u8,
+    pub account_age_days: i64,
+    pub lifetime_matches: u16,
+    pub lifetime_kd: Option<f32>,
+    pub lifetime_headshot_pct: Option<f32>,
+    /// Between `0.0` and `1.0`, like the win rate of the form.
+    pub lifetime_win_rate: Option<f32>,
+    pub form: Form,
+    pub maps: MapPerformance,
+}
+
+impl ComparedPlayer {
+    /// Expects the stats of `stats` and `matches` to be mapped, see [`MapStats`].
+    pub fn new(info: &PlayerInfo, stats: &Stats, matches: &Matches) -> ComparedPlayer {
+        ComparedPlayer {
+            id: info.id.clone(),
+            nickname: info.nickname.clone(),
+            country: info.country.clone(),
+            elo: info.elo,
+            skill_level: info.skill_level,
+            account_age_days: info.account_age(&Local::now()).num_days(),
+            lifetime_matches: stats.matches,
+            lifetime_kd: stats.kd(),
+            lifetime_headshot_pct: stats.headshot_pct(),
+            lifetime_win_rate: stats.win_rate().map(|rate| rate / 100.0),
+            form: Form::from_matches(matches, &FormOptions::default()),
+            maps: MapPerformance::from_matches(matches, Window::All),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct Difference {
+    pub metric: &'static str,
+    /// One value per player, in the order of the compared players.
+    pub values: Vec<Option<f32>>,
+    /// Difference to the first player relative to their value,
+    /// e.g. `0.1` if the player is 10% higher.
+    pub relative: Vec<Option<f32>>,
+}
+
+impl Difference {
+    fn new(metric: &'static str, values: Vec<Option<f32>>) -> Difference {
+        let base = values.first().copied().flatten().filter(|&b| b != 0.0);
+        let relative = values
+            .iter()
+            .map(|&value| Some((value? - base?) / base?))
+            .collect();
+        Difference {
+            metric,
+            values,
+            relative,
+        }
+    }
+}
+
+/// Side-by-side comparison of two or more players, relative to the first one.
+#[derive(Debug, Serialize)]
+pub struct Comparison {
+    pub players: Vec<ComparedPlayer>,
+    pub differences: Vec<Difference>,
+}
+
+impl Comparison {
+    pub fn new(players: Vec<ComparedPlayer>) -> Comparison {
+        type Metric = fn(&ComparedPlayer) -> Option<f32>;
+        let metrics: [(&'static str, Metric); 9] = [
+            ("elo", |p| Some(p.elo as f32)),
+            ("account_age_days", |p| Some(p.account_age_days as f32)),
+            ("lifetime_matches", |p| Some(p.lifetime_matches as f32)),
+            ("lifetime_kd", |p| p.lifetime_kd),
+            ("lifetime_headshot_pct", |p| p.lifetime_headshot_pct),
+            ("lifetime_win_rate", |p| p.lifetime_win_rate),
+            ("recent_win_rate", |p| p.form.last_n_win_rate),
+            ("recent_kd", |p| p.form.rolling_kd.last().copied().flatten()),
+            ("form_score", |p| Some(p.form.score)),
+        ];
+        let differences = metrics
+            .into_iter()
+            .map(|(name, metric)| Difference::new(name, players.iter().map(metric).collect()))
+            .collect();
+
+        Comparison {
+            players,
+            differences,
+        }
+    }
+}
+
+impl Client {
+    /// Fetches the profile, the lifetime stats and the last `recent` matches
+    /// of every player and compares them.
+    pub async fn compare(
+        &self,
+        player_ids: &[&str],
+        mapping: &Mapping,
+        recent: usize,
+    ) -> reqwest::Result<Comparison> {
+        let mut players = Vec::new();
+        for &player_id in player_ids {
+            let nickname = self.nickname(player_id).await?.nickname;
+            let (info, mut stats, mut matches) = futures::try_join!(
+                self.info(&nickname),
+                self.stats(player_id),
+                self.matches(player_id, recent, 0),
+            )?;
+            stats.map_stats(mapping);
+            matches.map_stats(mapping);
+            players.push(ComparedPlayer::new(&info, &stats, &matches));
+        }
+        Ok(Comparison::new(players))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn player(id: &str, elo: u16, kd: Option<f32>) -> ComparedPlayer {
+        let matches = Matches(Vec::new());
+        ComparedPlayer {
+            id: id.to_string(),
+            nickname: id.to_string(),
+            country: "de".to_string(),
+            elo,
+            skill_level: 10,
+            account_age_days: 100,
+            lifetime_matches: 0,
+            lifetime_kd: kd,
+            lifetime_headshot_pct: None,
+            lifetime_win_rate: None,
+            form: Form::from_matches(&matches, &FormOptions::default()),
+            maps: MapPerformance::from_matches(&matches, Window::All),
+        }
+    }
+
+    #[test]
+    fn relative_to_the_first_player() {
+        let players = vec![
+            player("a", 2000, Some(1.0)),
+            player("b", 2200, None),
+            player("c", 1500, Some(1.5)),
+        ];
+        let comparison = Comparison::new(players);
+        let difference = |metric| {
+            let mut differences = comparison.differences.iter();
+            differences.find(|d| d.metric == metric).unwrap()
+        };
+
+        let elo = difference("elo");
+        assert_eq!(elo.values, [Some(2000.0), Some(2200.0), Some(1500.0)]);
+        assert_eq!(elo.relative, [Some(0.0), Some(0.1), Some(-0.25)]);
+        assert_eq!(
+            difference("lifetime_kd").relative,
+            [Some(0.0), None, Some(0.5)]
+        );
+        // Nothing is relative to a value of zero.
+        assert_eq!(difference("lifetime_matches").relative, [None, None, None]);
+        assert_eq!(difference("recent_kd").values, [None, None, None]);
+    }
+}
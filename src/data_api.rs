@@ -0,0 +1,943 @@
+use crate::live::MatchStatus;
+use crate::matches::{Match, Matches};
+use crate::room::{MatchResult, Stats as TeamRating};
+use crate::room_stats::{MatchStats, PlayerStats, RoomStats, TeamStats};
+use crate::shared::{parse_stat, unix_time_ms, unix_time_sec};
+use crate::stats::Stats;
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+
+use chrono::{DateTime, Local};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+const API: &str = "https://open.faceit.com/data/v4";
+const GAME: &str = "csgo";
+
+/// Client for the documented Data API, authenticated with an api key
+/// from the faceit developer portal.
+///
+/// Stats returned by this api are already labeled, e.g. `Kills` or
+/// `K/D Ratio`, and must not be mapped with [`crate::MapStats`].
+#[derive(Clone)]
+pub struct DataClient {
+    pub client: reqwest::Client,
+    api_key: String,
+}
+
+impl Debug for DataClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DataClient")
+            .field("api_key", &"<redacted>")
+            .finish()
+    }
+}
+
+impl DataClient {
+    pub fn new(api_key: impl Into<String>) -> DataClient {
+        DataClient {
+            client: reqwest::Client::new(),
+            api_key: api_key.into(),
+        }
+    }
+
+    async fn get_json<T>(&self, path: &str, query: &[(&str, &str)]) -> reqwest::Result<T>
+    where
+        T: DeserializeOwned,
+    {
+        self.client
+            .get(format!("{}{}", API, path))
+            .bearer_auth(&self.api_key)
+            .query(query)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await
+    }
+}
+
+/// Every value as a string, like the values of the internal api.
+fn stringify(stats: HashMap<String, Value>) -> HashMap<String, String> {
+    let stats = stats.into_iter();
+    let stats = stats.filter_map(|(k, v)| match v {
+        Value::String(str) => Some((k, str)),
+        Value::Number(num) => Some((k, num.to_string())),
+        Value::Bool(b) => Some((k, b.to_string())),
+        _ => None,
+    });
+    stats.collect()
+}
+
+#[derive(Deserialize)]
+struct Page<T> {
+    items: Vec<T>,
+}
+
+#[derive(Deserialize)]
+struct Game {
+    region: String,
+    game_player_id: String,
+    game_player_name: String,
+    skill_level: u8,
+    faceit_elo: u16,
+}
+
+#[derive(Deserialize)]
+struct Player_ {
+    player_id: String,
+    nickname: String,
+    avatar: Option<String>,
+    country: String,
+    cover_image: Option<String>,
+    #[serde(default)]
+    games: HashMap<String, Game>,
+    #[serde(default)]
+    friends_ids: Vec<String>,
+    #[serde(default)]
+    memberships: Vec<String>,
+    activated_at: Option<String>,
+}
+
+/// A player as returned by the Data API.
+///
+/// Unlike [`crate::PlayerInfo`] it has no account creation date and no phone
+/// verification, the game fields are missing if the player never played csgo.
+#[derive(Debug, Serialize)]
+pub struct DataProfile {
+    pub id: String,
+    pub nickname: String,
+    pub country: String,
+    pub avatar: Option<String>,
+    pub cover_image: Option<String>,
+    /// When the account was activated, which may be long after it was created.
+    pub activated_at: Option<DateTime<Local>>,
+    pub friends: Vec<String>,
+    pub memberships: Vec<String>,
+    pub region: Option<String>,
+    pub game_id: Option<String>,
+    pub game_name: Option<String>,
+    pub elo: Option<u16>,
+    pub skill_level: Option<u8>,
+}
+
+impl From<Player_> for DataProfile {
+    fn from(mut player: Player_) -> DataProfile {
+        let game = player.games.remove(GAME);
+        let non_empty = |str: Option<String>| str.filter(|str| !str.is_empty());
+        let activated_at = player.activated_at.as_deref();
+        let activated_at = activated_at.and_then(|at| DateTime::parse_from_rfc3339(at).ok());
+
+        DataProfile {
+            id: player.player_id,
+            nickname: player.nickname,
+            country: player.country,
+            avatar: non_empty(player.avatar),
+            cover_image: non_empty(player.cover_image),
+            activated_at: activated_at.map(|at| at.with_timezone(&Local)),
+            friends: player.friends_ids,
+            memberships: player.memberships,
+            region: game.as_ref().map(|game| game.region.clone()),
+            game_id: game.as_ref().map(|game| game.game_player_id.clone()),
+            game_name: game.as_ref().map(|game| game.game_player_name.clone()),
+            elo: game.as_ref().map(|game| game.faceit_elo),
+            skill_level: game.as_ref().map(|game| game.skill_level),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct Segment_ {
+    label: String,
+    mode: String,
+    #[serde(rename = "type")]
+    kind: String,
+    stats: HashMap<String, Value>,
+}
+
+#[derive(Deserialize)]
+struct LifetimeStats_ {
+    lifetime: HashMap<String, Value>,
+    #[serde(default)]
+    segments: Vec<Segment_>,
+}
+
+/// Stats of a player on a single map and game mode.
+#[derive(Debug, Serialize)]
+pub struct Segment {
+    /// The map, e.g. `de_mirage`.
+    pub label: String,
+    pub mode: String,
+    pub kind: String,
+    pub stats: HashMap<String, String>,
+}
+
+#[derive(Debug)]
+pub struct LifetimeStats {
+    /// `updated_at` is the time the stats were fetched.
+    pub lifetime: Stats,
+    pub segments: Vec<Segment>,
+}
+
+impl From<LifetimeStats_> for LifetimeStats {
+    fn from(stats: LifetimeStats_) -> LifetimeStats {
+        let lifetime = stringify(stats.lifetime);
+        let segments = stats.segments.into_iter().map(|segment| Segment {
+            label: segment.label,
+            mode: segment.mode,
+            kind: segment.kind,
+            stats: stringify(segment.stats),
+        });
+
+        LifetimeStats {
+            lifetime: Stats {
+                matches: parse_stat(&lifetime, "Matches").unwrap_or_default(),
+                created_at: None,
+                updated_at: Local::now(),
+                stats: lifetime,
+            },
+            segments: segments.collect(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct GameStats_ {
+    stats: HashMap<String, Value>,
+}
+
+impl GameStats_ {
+    /// `None` if the entry has no date. The api has no team id, only the
+    /// name of the team in `Team`.
+    fn into_match(self) -> Option<Match> {
+        let stats = stringify(self.stats);
+        let get = |label: &str| stats.get(label).cloned().unwrap_or_default();
+        let date = parse_stat::<u64>(&stats, "Match Finished At").map(unix_time_ms);
+        let date = date.or_else(|| {
+            let created_at = DateTime::parse_from_rfc3339(stats.get("Created At")?).ok()?;
+            Some(created_at.with_timezone(&Local))
+        })?;
+
+        Some(Match {
+            match_id: get("Match Id"),
+            date,
+            elo: None,
+            premade: None,
+            team_id: None,
+            game_mode: get("Game Mode"),
+            best_of: get("Best Of"),
+            played: "1".to_string(),
+            status: "FINISHED".to_string(),
+            game: GAME.to_string(),
+            stats,
+        })
+    }
+}
+
+#[derive(Deserialize)]
+struct HistoryMatch_ {
+    match_id: String,
+    region: Option<String>,
+    game_mode: Option<String>,
+    competition_id: Option<String>,
+    competition_name: Option<String>,
+    competition_type: Option<String>,
+    organizer_id: Option<String>,
+    status: String,
+    started_at: Option<u64>,
+    finished_at: Option<u64>,
+    results: Option<MatchResult>,
+    #[serde(default)]
+    playing_players: Vec<String>,
+}
+
+/// An entry in the match history of a player, without stats.
+#[derive(Debug, Serialize)]
+pub struct HistoryMatch {
+    pub match_id: String,
+    pub region: Option<String>,
+    pub game_mode: Option<String>,
+    pub competition_id: Option<String>,
+    pub competition_name: Option<String>,
+    pub competition_type: Option<String>,
+    pub organizer_id: Option<String>,
+    pub status: MatchStatus,
+    pub started_at: Option<DateTime<Local>>,
+    pub finished_at: Option<DateTime<Local>>,
+    pub result: Option<MatchResult>,
+    /// Ids of every player in the match.
+    pub players: Vec<String>,
+}
+
+impl From<HistoryMatch_> for HistoryMatch {
+    fn from(m: HistoryMatch_) -> HistoryMatch {
+        HistoryMatch {
+            match_id: m.match_id,
+            region: m.region,
+            game_mode: m.game_mode,
+            competition_id: m.competition_id,
+            competition_name: m.competition_name,
+            competition_type: m.competition_type,
+            organizer_id: m.organizer_id,
+            status: MatchStatus::parse(&m.status),
+            started_at: m.started_at.map(unix_time_sec),
+            finished_at: m.finished_at.map(unix_time_sec),
+            result: m.results,
+            players: m.playing_players,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct RosterPlayer {
+    player_id: String,
+    nickname: String,
+    avatar: Option<String>,
+    game_player_id: Option<String>,
+    game_skill_level: Option<u8>,
+    membership: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct TeamStats_ {
+    rating: u16,
+    #[serde(rename = "winProbability")]
+    win_probability: f32,
+}
+
+#[derive(Deserialize)]
+struct Team_ {
+    faction_id: String,
+    name: String,
+    leader: Option<String>,
+    avatar: Option<String>,
+    #[serde(default)]
+    roster: Vec<RosterPlayer>,
+    stats: Option<TeamStats_>,
+}
+
+#[derive(Deserialize, Default)]
+struct Pick {
+    #[serde(default)]
+    pick: Vec<String>,
+}
+
+#[derive(Deserialize, Default)]
+struct Voting {
+    #[serde(default)]
+    map: Pick,
+    #[serde(default)]
+    location: Pick,
+}
+
+#[derive(Deserialize)]
+struct DataMatch_ {
+    match_id: String,
+    game: String,
+    region: Option<String>,
+    competition_id: Option<String>,
+    competition_type: Option<String>,
+    competition_name: Option<String>,
+    organizer_id: Option<String>,
+    status: String,
+    best_of: Option<u8>,
+    teams: HashMap<String, Team_>,
+    voting: Option<Voting>,
+    configured_at: Option<u64>,
+    started_at: Option<u64>,
+    finished_at: Option<u64>,
+    results: Option<MatchResult>,
+    #[serde(default)]
+    demo_url: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DataPlayer {
+    pub id: String,
+    pub nickname: String,
+    pub avatar: Option<String>,
+    pub game_id: Option<String>,
+    pub skill_level: Option<u8>,
+    pub membership: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DataTeam {
+    pub id: String,
+    pub name: String,
+    pub leader: Option<String>,
+    pub avatar: Option<String>,
+    pub roster: Vec<DataPlayer>,
+    pub stats: Option<TeamRating>,
+}
+
+/// A room as returned by the Data API, which lacks the elo of the players.
+#[derive(Debug, Serialize)]
+pub struct DataMatch {
+    /// The room id, see [`crate::Client::room`].
+    pub id: String,
+    pub game: String,
+    pub region: Option<String>,
+    pub competition_id: Option<String>,
+    pub competition_type: Option<String>,
+    pub competition_name: Option<String>,
+    pub organizer_id: Option<String>,
+    pub status: MatchStatus,
+    pub best_of: u8,
+    pub maps: Vec<String>,
+    pub location: Option<String>,
+    pub faction_1: Option<DataTeam>,
+    pub faction_2: Option<DataTeam>,
+    pub configured_at: Option<DateTime<Local>>,
+    pub started_at: Option<DateTime<Local>>,
+    pub finished_at: Option<DateTime<Local>>,
+    pub result: Option<MatchResult>,
+    pub demo_urls: Vec<String>,
+}
+
+impl From<Team_> for DataTeam {
+    fn from(team: Team_) -> DataTeam {
+        let roster = team.roster.into_iter().map(|p| DataPlayer {
+            id: p.player_id,
+            nickname: p.nickname,
+            avatar: p.avatar,
+            game_id: p.game_player_id,
+            skill_level: p.game_skill_level,
+            membership: p.membership,
+        });
+
+        DataTeam {
+            id: team.faction_id,
+            name: team.name,
+            leader: team.leader,
+            avatar: team.avatar,
+            roster: roster.collect(),
+            stats: team.stats.map(|stats| TeamRating {
+                win_probability: stats.win_probability,
+                rating: stats.rating,
+            }),
+        }
+    }
+}
+
+impl From<DataMatch_> for DataMatch {
+    fn from(mut m: DataMatch_) -> DataMatch {
+        let voting = m.voting.unwrap_or_default();
+
+        DataMatch {
+            id: m.match_id,
+            game: m.game,
+            region: m.region,
+            competition_id: m.competition_id,
+            competition_type: m.competition_type,
+            competition_name: m.competition_name,
+            organizer_id: m.organizer_id,
+            status: MatchStatus::parse(&m.status),
+            best_of: m.best_of.unwrap_or(1),
+            maps: voting.map.pick,
+            location: voting.location.pick.into_iter().next(),
+            faction_1: m.teams.remove("faction1").map(DataTeam::from),
+            faction_2: m.teams.remove("faction2").map(DataTeam::from),
+            configured_at: m.configured_at.map(unix_time_sec),
+            started_at: m.started_at.map(unix_time_sec),
+            finished_at: m.finished_at.map(unix_time_sec),
+            result: m.results,
+            demo_urls: m.demo_url,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct PlayerRound {
+    player_id: String,
+    nickname: String,
+    player_stats: HashMap<String, String>,
+}
+
+#[derive(Deserialize)]
+struct TeamRound {
+    team_id: String,
+    #[serde(default)]
+    premade: bool,
+    team_stats: HashMap<String, String>,
+    players: Vec<PlayerRound>,
+}
+
+#[derive(Deserialize)]
+struct Round {
+    best_of: String,
+    game_id: String,
+    game_mode: String,
+    match_id: String,
+    match_round: String,
+    played: String,
+    round_stats: HashMap<String, String>,
+    teams: Vec<TeamRound>,
+}
+
+#[derive(Deserialize)]
+struct RoomStats_ {
+    rounds: Vec<Round>,
+}
+
+impl RoomStats_ {
+    /// The api has no date per map, the date of the match is used instead.
+    fn into_room_stats(self, date: DateTime<Local>) -> RoomStats {
+        let rounds = self.rounds.into_iter().map(|round| {
+            let teams = round.teams.into_iter().map(|team| TeamStats {
+                team_id: team.team_id,
+                premade: team.premade,
+                players: team
+                    .players
+                    .into_iter()
+                    .map(|p| PlayerStats {
+                        nickname: p.nickname,
+                        player_id: p.player_id,
+                        stats: p.player_stats,
+                    })
+                    .collect(),
+                stats: team.team_stats,
+            });
+
+            MatchStats {
+                date,
+                best_of: round.best_of.parse().unwrap_or(1),
+                round: round.match_round.parse().unwrap_or(1),
+                game: round.game_id,
+                game_mode: round.game_mode,
+                match_id: round.match_id,
+                played: round.played,
+                teams: teams.collect(),
+                stats: round.round_stats,
+            }
+        });
+        RoomStats(rounds.collect())
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct Hub {
+    #[serde(rename = "hub_id")]
+    pub id: String,
+    pub name: String,
+    pub avatar: Option<String>,
+    pub cover_image: Option<String>,
+    pub description: Option<String>,
+    pub game_id: Option<String>,
+    pub region: Option<String>,
+    pub organizer_id: Option<String>,
+    pub players_joined: Option<u32>,
+    pub min_skill_level: Option<u8>,
+    pub max_skill_level: Option<u8>,
+    pub faceit_url: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct HubMember {
+    #[serde(rename = "user_id")]
+    pub id: String,
+    pub nickname: String,
+    pub avatar: Option<String>,
+    /// Role ids, e.g. `owner`, `admin` or `default`.
+    #[serde(default)]
+    pub roles: Vec<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct Championship {
+    #[serde(rename = "championship_id")]
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub game_id: Option<String>,
+    pub region: Option<String>,
+    pub organizer_id: Option<String>,
+    pub status: Option<String>,
+    #[serde(rename = "type")]
+    pub kind: Option<String>,
+    pub slots: Option<u32>,
+    pub current_subscriptions: Option<u32>,
+    pub total_rounds: Option<u32>,
+    pub faceit_url: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct RankingEntry {
+    pub position: u64,
+    pub player_id: String,
+    pub nickname: String,
+    pub country: Option<String>,
+    pub skill_level: u8,
+    #[serde(rename = "faceit_elo")]
+    pub elo: u16,
+}
+
+#[derive(Deserialize)]
+struct PlayerRanking_ {
+    position: u64,
+}
+
+impl DataClient {
+    /// - `https://open.faceit.com/data/v4/players/{PLAYER_ID}`
+    pub async fn player(&self, player_id: &str) -> reqwest::Result<DataProfile> {
+        let path = format!("/players/{}", player_id);
+        Ok(self.get_json::<Player_>(&path, &[]).await?.into())
+    }
+
+    /// - `https://open.faceit.com/data/v4/players?nickname={NICKNAME}`
+    pub async fn player_by_nickname(&self, nickname: &str) -> reqwest::Result<DataProfile> {
+        let query = [("nickname", nickname)];
+        Ok(self.get_json::<Player_>("/players", &query).await?.into())
+    }
+
+    /// - `https://open.faceit.com/data/v4/players/{PLAYER_ID}/stats/csgo`
+    pub async fn player_stats(&self, player_id: &str) -> reqwest::Result<LifetimeStats> {
+        let path = format!("/players/{}/stats/{}", player_id, GAME);
+        Ok(self.get_json::<LifetimeStats_>(&path, &[]).await?.into())
+    }
+
+    /// - `https://open.faceit.com/data/v4/players/{PLAYER_ID}/history`
+    pub async fn player_history(
+        &self,
+        player_id: &str,
+        offset: usize,
+        limit: usize,
+    ) -> reqwest::Result<Vec<HistoryMatch>> {
+        let path = format!("/players/{}/history", player_id);
+        let (offset, limit) = (offset.to_string(), limit.to_string());
+        let query = [("game", GAME), ("offset", &offset), ("limit", &limit)];
+
+        let page = self.get_json::<Page<HistoryMatch_>>(&path, &query).await?;
+        Ok(page.items.into_iter().map(HistoryMatch::from).collect())
+    }
+
+    /// The stats of the last matches of a player, newest first and without
+    /// elo or team id. Entries without a date are skipped.
+    ///
+    /// - `https://open.faceit.com/data/v4/players/{PLAYER_ID}/games/csgo/stats`
+    pub async fn player_matches(
+        &self,
+        player_id: &str,
+        offset: usize,
+        limit: usize,
+    ) -> reqwest::Result<Matches> {
+        let path = format!("/players/{}/games/{}/stats", player_id, GAME);
+        let (offset, limit) = (offset.to_string(), limit.to_string());
+        let query = [("offset", offset.as_str()), ("limit", &limit)];
+
+        let page = self.get_json::<Page<GameStats_>>(&path, &query).await?;
+        let matches = page.items.into_iter().filter_map(GameStats_::into_match);
+        Ok(Matches(matches.collect()))
+    }
+
+    /// - `https://open.faceit.com/data/v4/matches/{MATCH_ID}`
+    pub async fn match_details(&self, match_id: &str) -> reqwest::Result<DataMatch> {
+        let path = format!("/matches/{}", match_id);
+        Ok(self.get_json::<DataMatch_>(&path, &[]).await?.into())
+    }
+
+    /// - `https://open.faceit.com/data/v4/matches/{MATCH_ID}`
+    /// - `https://open.faceit.com/data/v4/matches/{MATCH_ID}/stats`
+    pub async fn match_stats(&self, match_id: &str) -> reqwest::Result<RoomStats> {
+        let path = format!("/matches/{}/stats", match_id);
+        let (details, stats) = futures::try_join!(
+            self.match_details(match_id),
+            self.get_json::<RoomStats_>(&path, &[]),
+        )?;
+
+        let date = details.finished_at.or(details.started_at);
+        Ok(stats.into_room_stats(date.unwrap_or_else(Local::now)))
+    }
+
+    /// - `https://open.faceit.com/data/v4/hubs/{HUB_ID}`
+    pub async fn hub(&self, hub_id: &str) -> reqwest::Result<Hub> {
+        let path = format!("/hubs/{}", hub_id);
+        self.get_json(&path, &[]).await
+    }
+
+    /// - `https://open.faceit.com/data/v4/hubs/{HUB_ID}/members`
+    pub async fn hub_members(
+        &self,
+        hub_id: &str,
+        offset: usize,
+        limit: usize,
+    ) -> reqwest::Result<Vec<HubMember>> {
+        let path = format!("/hubs/{}/members", hub_id);
+        let (offset, limit) = (offset.to_string(), limit.to_string());
+        let query = [("offset", offset.as_str()), ("limit", &limit)];
+
+        Ok(self.get_json::<Page<HubMember>>(&path, &query).await?.items)
+    }
+
+    /// Past matches of the hub, newest first.
+    ///
+    /// - `https://open.faceit.com/data/v4/hubs/{HUB_ID}/matches`
+    pub async fn hub_matches(
+        &self,
+        hub_id: &str,
+        offset: usize,
+        limit: usize,
+    ) -> reqwest::Result<Vec<DataMatch>> {
+        let path = format!("/hubs/{}/matches", hub_id);
+        let (offset, limit) = (offset.to_string(), limit.to_string());
+        let query = [("type", "past"), ("offset", &offset), ("limit", &limit)];
+
+        let page = self.get_json::<Page<DataMatch_>>(&path, &query).await?;
+        Ok(page.items.into_iter().map(DataMatch::from).collect())
+    }
+
+    /// - `https://open.faceit.com/data/v4/championships/{CHAMPIONSHIP_ID}`
+    pub async fn championship(&self, championship_id: &str) -> reqwest::Result<Championship> {
+        let path = format!("/championships/{}", championship_id);
+        self.get_json(&path, &[]).await
+    }
+
+    /// - `https://open.faceit.com/data/v4/championships/{CHAMPIONSHIP_ID}/matches`
+    pub async fn championship_matches(
+        &self,
+        championship_id: &str,
+        offset: usize,
+        limit: usize,
+    ) -> reqwest::Result<Vec<DataMatch>> {
+        let path = format!("/championships/{}/matches", championship_id);
+        let (offset, limit) = (offset.to_string(), limit.to_string());
+        let query = [("type", "all"), ("offset", &offset), ("limit", &limit)];
+
+        let page = self.get_json::<Page<DataMatch_>>(&path, &query).await?;
+        Ok(page.items.into_iter().map(DataMatch::from).collect())
+    }
+
+    /// The global ranking of a region, e.g. `EU`, or of a country
+    /// within the region, e.g. `de`.
+    ///
+    /// - `https://open.faceit.com/data/v4/rankings/games/csgo/regions/{REGION}`
+    pub async fn rankings(
+        &self,
+        region: &str,
+        country: Option<&str>,
+        offset: usize,
+        limit: usize,
+    ) -> reqwest::Result<Vec<RankingEntry>> {
+        let path = format!("/rankings/games/{}/regions/{}", GAME, region);
+        let (offset, limit) = (offset.to_string(), limit.to_string());
+        let mut query = vec![("offset", offset.as_str()), ("limit", &limit)];
+        query.extend(country.map(|country| ("country", country)));
+
+        Ok(self
+            .get_json::<Page<RankingEntry>>(&path, &query)
+            .await?
+            .items)
+    }
+
+    /// The position of the player in the ranking of the region.
+    ///
+    /// - `https://open.faceit.com/data/v4/rankings/games/csgo/regions/{REGION}/players/{PLAYER_ID}`
+    pub async fn player_ranking(&self, region: &str, player_id: &str) -> reqwest::Result<u64> {
+        let path = format!(
+            "/rankings/games/{}/regions/{}/players/{}",
+            GAME, region, player_id
+        );
+        let query = [("limit", "1")];
+
+        Ok(self
+            .get_json::<PlayerRanking_>(&path, &query)
+            .await?
+            .position)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn player() {
+        let json = r#"{
+            "player_id": "p1",
+            "nickname": "s1mple",
+            "avatar": "",
+            "country": "ua",
+            "cover_image": "https://assets.faceit.com/cover.jpg",
+            "games": {
+                "csgo": {
+                    "region": "EU",
+                    "game_player_id": "76561198034202275",
+                    "game_player_name": "s1mple",
+                    "skill_level": 10,
+                    "faceit_elo": 3500
+                }
+            },
+            "friends_ids": ["p2"],
+            "memberships": ["free"],
+            "activated_at": "2014-05-19T12:00:00Z"
+        }"#;
+        let player: DataProfile = serde_json::from_str::<Player_>(json).unwrap().into();
+
+        assert_eq!(player.avatar, None);
+        assert_eq!(player.elo, Some(3500));
+        assert_eq!(player.region.as_deref(), Some("EU"));
+        assert_eq!(player.friends, ["p2"]);
+        assert!(player.activated_at.is_some());
+
+        let json = r#"{ "player_id": "p2", "nickname": "new", "country": "de" }"#;
+        let player: DataProfile = serde_json::from_str::<Player_>(json).unwrap().into();
+        assert_eq!((player.elo, player.activated_at), (None, None));
+    }
+
+    #[test]
+    fn lifetime_stats() {
+        let json = r#"{
+            "player_id": "p1",
+            "game_id": "csgo",
+            "lifetime": {
+                "Matches": "120",
+                "Average K/D Ratio": "1.25",
+                "Win Rate %": "55",
+                "Recent Results": ["1", "0"]
+            },
+            "segments": [{
+                "label": "de_mirage",
+                "mode": "5v5",
+                "type": "Map",
+                "stats": { "Matches": "40", "K/D Ratio": "1.3", "Wins": 22 }
+            }]
+        }"#;
+        let stats: LifetimeStats = serde_json::from_str::<LifetimeStats_>(json).unwrap().into();
+
+        assert_eq!(stats.lifetime.matches, 120);
+        assert_eq!(stats.lifetime.kd(), Some(1.25));
+        assert_eq!(stats.lifetime.win_rate(), Some(55.0));
+        assert!(!stats.lifetime.stats.contains_key("Recent Results"));
+        assert_eq!(stats.segments[0].label, "de_mirage");
+        assert_eq!(stats.segments[0].stats["Wins"], "22");
+    }
+
+    #[test]
+    fn game_stats() {
+        let json = r#"[
+            { "stats": {
+                "Match Id": "1-m1",
+                "Match Finished At": 1662057720000,
+                "Team": "team_s1mple",
+                "Game Mode": "5v5",
+                "Best Of": "1",
+                "Map": "de_mirage",
+                "Result": "1",
+                "Kills": "25"
+            } },
+            { "stats": { "Match Id": "1-m2", "Kills": "10" } }
+        ]"#;
+        let stats = serde_json::from_str::<Vec<GameStats_>>(json).unwrap();
+        let matches = stats.into_iter().filter_map(GameStats_::into_match);
+        let matches = matches.collect::<Vec<_>>();
+
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].match_id, "1-m1");
+        assert_eq!(matches[0].date.timestamp(), 1662057720);
+        assert_eq!(matches[0].team_id, None);
+        assert_eq!(matches[0].won(), Some(true));
+        assert_eq!(matches[0].kills(), Some(25));
+    }
+
+    #[test]
+    fn history() {
+        let json = r#"{
+            "items": [{
+                "match_id": "1-m1",
+                "region": "EU",
+                "game_mode": "5v5",
+                "competition_id": "c1",
+                "competition_type": "matchmaking",
+                "status": "finished",
+                "started_at": 1662055200,
+                "finished_at": 1662057720,
+                "results": { "winner": "faction2", "score": { "faction1": 0, "faction2": 1 } },
+                "playing_players": ["p1", "p2"]
+            }],
+            "start": 0,
+            "end": 1
+        }"#;
+        let page = serde_json::from_str::<Page<HistoryMatch_>>(json).unwrap();
+        let history = HistoryMatch::from(page.items.into_iter().next().unwrap());
+
+        assert_eq!(history.status, MatchStatus::Finished);
+        assert_eq!(history.finished_at.unwrap().timestamp(), 1662057720);
+        assert_eq!(history.result.unwrap().winner, "faction2");
+        assert_eq!(history.players, ["p1", "p2"]);
+    }
+
+    #[test]
+    fn match_details() {
+        let json = r#"{
+            "match_id": "1-m1",
+            "game": "csgo",
+            "region": "EU",
+            "status": "ONGOING",
+            "best_of": 3,
+            "teams": {
+                "faction1": {
+                    "faction_id": "f1",
+                    "name": "team_a",
+                    "leader": "p1",
+                    "roster": [{ "player_id": "p1", "nickname": "a", "game_skill_level": 10 }],
+                    "stats": { "rating": 2400, "winProbability": 0.55, "skillLevel": { "average": 9 } }
+                },
+                "faction2": { "faction_id": "f2", "name": "team_b", "roster": [] }
+            },
+            "voting": { "map": { "pick": ["de_mirage", "de_inferno"] }, "location": { "pick": ["Frankfurt"] } },
+            "started_at": 1662055200
+        }"#;
+        let m: DataMatch = serde_json::from_str::<DataMatch_>(json).unwrap().into();
+
+        assert_eq!(m.status, MatchStatus::Ongoing);
+        assert_eq!(m.best_of, 3);
+        assert_eq!(m.maps, ["de_mirage", "de_inferno"]);
+        assert_eq!(m.location.as_deref(), Some("Frankfurt"));
+        assert_eq!(m.finished_at, None);
+        assert!(m.result.is_none());
+
+        let faction_1 = m.faction_1.unwrap();
+        assert_eq!(faction_1.roster[0].skill_level, Some(10));
+        assert_eq!(faction_1.stats.unwrap().rating, 2400);
+        assert!(m.faction_2.unwrap().stats.is_none());
+    }
+
+    #[test]
+    fn match_stats() {
+        let json = r#"{
+            "rounds": [{
+                "best_of": "3",
+                "game_id": "csgo",
+                "game_mode": "5v5",
+                "match_id": "1-m1",
+                "match_round": "2",
+                "played": "1",
+                "round_stats": { "Map": "de_inferno", "Score": "16 / 12", "Winner": "f1" },
+                "teams": [{
+                    "team_id": "f1",
+                    "premade": true,
+                    "team_stats": { "Team": "team_a", "Team Win": "1", "Final Score": "16" },
+                    "players": [
+                        { "player_id": "p1", "nickname": "a", "player_stats": { "Kills": "30", "Deaths": "15" } },
+                        { "player_id": "p2", "nickname": "b", "player_stats": { "Kills": "10", "Deaths": "20" } }
+                    ]
+                }]
+            }]
+        }"#;
+        let date = unix_time_sec(1662057720);
+        let stats = serde_json::from_str::<RoomStats_>(json).unwrap();
+        let stats = stats.into_room_stats(date);
+
+        let map = &stats.0[0];
+        assert_eq!((map.best_of, map.round), (3, 2));
+        assert_eq!(map.date, date);
+        assert_eq!(map.stats["Map"], "de_inferno");
+
+        let result = map.teams[0].result();
+        assert_eq!(result.won, Some(true));
+        assert_eq!(result.final_score, Some(16));
+        assert_eq!((result.kills, result.deaths), (40, 35));
+    }
+}
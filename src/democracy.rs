@@ -3,12 +3,12 @@ use crate::client::Client;
 use serde::{Deserialize, Serialize};
 
 #[derive(Deserialize, Serialize, Debug)]
-struct PickBan {
-    guid: String,
-    status: String,
-    random: bool,
-    round: u8,
-    selected_by: String,
+pub struct PickBan {
+    pub guid: String,
+    pub status: String,
+    pub random: bool,
+    pub round: u8,
+    pub selected_by: String,
 }
 
 #[derive(Deserialize)]
@@ -31,8 +31,14 @@ struct Response {
 
 #[derive(Serialize, Debug)]
 pub struct Democracy {
-    match_id: String,
-    map_veto: Vec<PickBan>,
+    pub match_id: String,
+    pub map_veto: Vec<PickBan>,
+}
+
+impl Democracy {
+    pub fn map_pool(&self) -> impl Iterator<Item = &str> {
+        self.map_veto.iter().map(|pick_ban| pick_ban.guid.as_str())
+    }
 }
 
 impl Into<Democracy> for Response {
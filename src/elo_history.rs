@@ -0,0 +1,213 @@
+use crate::matches::Match;
+
+use chrono::{DateTime, Local};
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Streak {
+    pub won: bool,
+    pub length: usize,
+}
+
+impl Streak {
+    /// Counts how many outcomes at the end of the sequence are equal.
+    pub(crate) fn current<I>(outcomes: I) -> Option<Streak>
+    where
+        I: IntoIterator<Item = bool>,
+        I::IntoIter: DoubleEndedIterator,
+    {
+        let mut outcomes = outcomes.into_iter().rev();
+        let won = outcomes.next()?;
+        let length = 1 + outcomes.take_while(|&o| o == won).count();
+        Some(Streak { won, length })
+    }
+
+    /// The longest run of equal outcomes for both wins and losses.
+    pub(crate) fn longest<I>(outcomes: I) -> (Option<Streak>, Option<Streak>)
+    where
+        I: IntoIterator<Item = bool>,
+    {
+        let (mut longest_win, mut longest_loss) = (None::<Streak>, None::<Streak>);
+        let mut current = None::<Streak>;
+        for won in outcomes {
+            let streak = match current {
+                Some(s) if s.won == won => Streak {
+                    won,
+                    length: s.length + 1,
+                },
+                _ => Streak { won, length: 1 },
+            };
+            let longest = if won {
+                &mut longest_win
+            } else {
+                &mut longest_loss
+            };
+            if longest.is_none_or(|l| l.length < streak.length) {
+                *longest = Some(streak);
+            }
+            current = Some(streak);
+        }
+        (longest_win, longest_loss)
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EloPoint {
+    pub match_id: String,
+    pub date: DateTime<Local>,
+    pub elo: u16,
+    /// Change to the previous point, `None` for the first one.
+    pub delta: Option<i32>,
+    /// The api reported no elo for this match, so it was interpolated
+    /// from the surrounding matches.
+    pub filled: bool,
+    pub won: Option<bool>,
+}
+
+/// Elo after each match, oldest first.
+#[derive(Debug, Clone, Serialize)]
+#[serde(transparent)]
+pub struct EloHistory(pub Vec<EloPoint>);
+
+impl EloHistory {
+    /// Builds the history from matches in any order.
+    ///
+    /// Missing elo values between two known ones are interpolated linearly,
+    /// missing values at either end take the value of the nearest known one.
+    /// The history is empty if no match has an elo.
+    pub fn from_matches<'a, I>(matches: I) -> EloHistory
+    where
+        I: IntoIterator<Item = &'a Match>,
+    {
+        let mut matches = matches.into_iter().collect::<Vec<_>>();
+        matches.sort_by_key(|m| m.date);
+
+        let known = matches
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, m)| Some((idx, m.elo?)))
+            .collect::<Vec<_>>();
+        let (first, last) = match (known.first(), known.last()) {
+            (Some(&first), Some(&last)) => (first, last),
+            _ => return EloHistory(Vec::new()),
+        };
+
+        let mut elos = vec![first.1 as f32; matches.len()];
+        for pair in known.windows(2) {
+            let ((from_idx, from), (to_idx, to)) = (pair[0], pair[1]);
+            let step = (to as f32 - from as f32) / (to_idx - from_idx) as f32;
+            for (offset, elo) in elos[from_idx..to_idx].iter_mut().enumerate() {
+                *elo = from as f32 + step * offset as f32;
+            }
+        }
+        elos[last.0..].fill(last.1 as f32);
+
+        let mut prev = None::<u16>;
+        let points = matches
+            .into_iter()
+            .zip(elos)
+            .map(|(m, elo)| {
+                let elo = elo.round() as u16;
+                let delta = prev.map(|prev| elo as i32 - prev as i32);
+                prev = Some(elo);
+                EloPoint {
+                    match_id: m.match_id.clone(),
+                    date: m.date,
+                    elo,
+                    delta,
+                    filled: m.elo.is_none(),
+                    won: m.won(),
+                }
+            })
+            .collect();
+        EloHistory(points)
+    }
+
+    pub fn current(&self) -> Option<u16> {
+        self.0.last().map(|p| p.elo)
+    }
+
+    /// The highest elo reached, the earliest point if there are several.
+    pub fn peak(&self) -> Option<&EloPoint> {
+        self.0.iter().rev().max_by_key(|p| p.elo)
+    }
+
+    /// The lowest elo reached, the earliest point if there are several.
+    pub fn low(&self) -> Option<&EloPoint> {
+        self.0.iter().min_by_key(|p| p.elo)
+    }
+
+    /// Average over the last `window` points for each point. The first
+    /// points average over fewer values.
+    pub fn rolling_average(&self, window: usize) -> Vec<f32> {
+        let window = window.max(1);
+        let elos = self.0.iter().map(|p| p.elo as f32).collect::<Vec<_>>();
+        (0..elos.len())
+            .map(|idx| {
+                let slice = &elos[(idx + 1).saturating_sub(window)..=idx];
+                slice.iter().sum::<f32>() / slice.len() as f32
+            })
+            .collect()
+    }
+
+    /// Uses the match result if it is known, the sign of the elo change
+    /// otherwise. Points without either are skipped.
+    pub fn current_streak(&self) -> Option<Streak> {
+        let outcomes = self.0.iter().filter_map(|p| match (p.won, p.delta) {
+            (Some(won), _) => Some(won),
+            (None, Some(delta)) if delta != 0 => Some(delta > 0),
+            _ => None,
+        });
+        Streak::current(outcomes.collect::<Vec<_>>())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::shared::unix_time_sec;
+
+    fn match_at(day: u64, elo: Option<u16>) -> Match {
+        let date = unix_time_sec(day * 24 * 60 * 60);
+        Match::fixture(
+            &format!("match-{}", day),
+            date,
+            elo,
+            Vec::<(&str, &str)>::new(),
+        )
+    }
+
+    #[test]
+    fn fills_gaps() {
+        let matches = [
+            match_at(5, None),
+            match_at(4, Some(1030)),
+            match_at(3, None),
+            match_at(2, Some(1010)),
+            match_at(1, None),
+        ];
+        let history = EloHistory::from_matches(&matches);
+
+        let elos = history.0.iter().map(|p| p.elo).collect::<Vec<_>>();
+        assert_eq!(elos, [1010, 1010, 1020, 1030, 1030]);
+        let filled = history.0.iter().map(|p| p.filled).collect::<Vec<_>>();
+        assert_eq!(filled, [true, false, true, false, true]);
+        assert_eq!(history.peak().unwrap().match_id, "match-4");
+        assert_eq!(history.low().unwrap().match_id, "match-1");
+        assert_eq!(
+            history.current_streak(),
+            Some(Streak {
+                won: true,
+                length: 2
+            })
+        );
+    }
+
+    #[test]
+    fn longest_streaks() {
+        let (win, loss) = Streak::longest([true, false, false, true, true, true, false]);
+        assert_eq!(win.unwrap().length, 3);
+        assert_eq!(loss.unwrap().length, 2);
+    }
+}
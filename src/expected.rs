@@ -0,0 +1,108 @@
+use crate::room::{Room, Team};
+
+use serde::Serialize;
+
+/// Elo points FACEIT distributes per match, the winner gains what the
+/// loser loses and both add up to this.
+const ELO_AT_STAKE: f32 = 50.0;
+
+/// Probability that a team with `elo` beats a team with `opponent_elo`
+/// according to the standard elo formula.
+pub fn win_probability(elo: f32, opponent_elo: f32) -> f32 {
+    1.0 / (1.0 + 10f32.powf((opponent_elo - elo) / 400.0))
+}
+
+#[derive(Debug, Serialize)]
+pub struct FactionExpectation {
+    pub team_id: String,
+    pub average_elo: f32,
+    pub win_probability: f32,
+    pub elo_gain: u16,
+    pub elo_loss: u16,
+    /// The win probability FACEIT reported for the team, if any.
+    pub reported_win_probability: Option<f32>,
+}
+
+impl FactionExpectation {
+    fn new(team: &Team, opponent: &Team) -> FactionExpectation {
+        let average_elo = team.average_elo();
+        let win_probability = win_probability(average_elo, opponent.average_elo());
+        let elo_gain = (ELO_AT_STAKE * (1.0 - win_probability)).round() as u16;
+
+        FactionExpectation {
+            team_id: team.id.clone(),
+            average_elo,
+            win_probability,
+            elo_gain,
+            elo_loss: ELO_AT_STAKE as u16 - elo_gain,
+            reported_win_probability: team.stats.as_ref().map(|s| s.win_probability),
+        }
+    }
+
+    /// Estimated minus reported win probability.
+    pub fn probability_difference(&self) -> Option<f32> {
+        Some(self.win_probability - self.reported_win_probability?)
+    }
+}
+
+/// Expected outcome of a room computed from the average elo of the rosters.
+#[derive(Debug, Serialize)]
+pub struct Expectation {
+    pub faction_1: FactionExpectation,
+    pub faction_2: FactionExpectation,
+}
+
+impl Room {
+    /// Only depends on the rosters, so it is known as soon as the room is created.
+    pub fn expectation(&self) -> Expectation {
+        let teams = &self.teams;
+        Expectation {
+            faction_1: FactionExpectation::new(&teams.faction_1, &teams.faction_2),
+            faction_2: FactionExpectation::new(&teams.faction_2, &teams.faction_1),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::room::Stats;
+
+    #[test]
+    fn probabilities() {
+        assert_eq!(win_probability(2000.0, 2000.0), 0.5);
+        assert!((win_probability(2400.0, 2000.0) - 10.0 / 11.0).abs() < 1e-6);
+
+        let p = win_probability(1800.0, 2100.0);
+        assert!((p + win_probability(2100.0, 1800.0) - 1.0).abs() < 1e-6);
+        let gain = (ELO_AT_STAKE * (1.0 - p)).round();
+        assert_eq!(gain, 42.0);
+    }
+    #[test]
+    fn expectation_of_a_room() {
+        let mut room = Room::fixture("room", &["a", "b"], &["c", "d"]);
+        for player in room.teams.faction_1.roster.iter_mut() {
+            player.elo = 2400;
+        }
+        for player in room.teams.faction_2.roster.iter_mut() {
+            player.elo = 2000;
+        }
+        room.teams.faction_1.stats = Some(Stats {
+            win_probability: 0.8,
+            rating: 2400,
+        });
+        let expectation = room.expectation();
+
+        let favorite = &expectation.faction_1;
+        assert_eq!(favorite.average_elo, 2400.0);
+        assert!((favorite.win_probability - 10.0 / 11.0).abs() < 1e-6);
+        assert_eq!((favorite.elo_gain, favorite.elo_loss), (5, 45));
+        let difference = favorite.probability_difference().unwrap();
+        assert!((difference - (10.0 / 11.0 - 0.8)).abs() < 1e-6);
+
+        let underdog = &expectation.faction_2;
+        assert_eq!(underdog.team_id, "2");
+        assert_eq!((underdog.elo_gain, underdog.elo_loss), (45, 5));
+        assert_eq!(underdog.probability_difference(), None);
+    }
+}
@@ -0,0 +1,171 @@
+use crate::elo_history::Streak;
+use crate::matches::{Match, Matches};
+use crate::shared::mean;
+
+use serde::Serialize;
+
+#[derive(Debug, Clone)]
+pub struct FormOptions {
+    /// How many recent matches the win rate and form score consider.
+    pub last_n: usize,
+    /// How many matches the rolling K/D and K/R average over.
+    pub rolling_window: usize,
+    /// Weight factor per match going back in time for the form score,
+    /// `1.0` weighs every match equally.
+    pub decay: f32,
+}
+
+impl Default for FormOptions {
+    fn default() -> FormOptions {
+        FormOptions {
+            last_n: 10,
+            rolling_window: 5,
+            decay: 0.85,
+        }
+    }
+}
+
+/// Recent trend of a player computed from the match history.
+///
+/// Expects the stats of the matches to be mapped, see [`Matches::map_stats`].
+#[derive(Debug, Serialize)]
+pub struct Form {
+    pub matches: usize,
+    pub current_streak: Option<Streak>,
+    pub longest_win_streak: usize,
+    pub longest_loss_streak: usize,
+    pub last_n_win_rate: Option<f32>,
+    /// Rolling K/D for each match, oldest first. `None` if no match
+    /// in the window has a K/D.
+    pub rolling_kd: Vec<Option<f32>>,
+    /// Rolling K/R, see `rolling_kd`.
+    pub rolling_kr: Vec<Option<f32>>,
+    /// Between `-1.0` (losing every match with a bad K/D)
+    /// and `1.0` (winning every match with a good K/D).
+    pub score: f32,
+}
+
+fn rolling(values: &[Option<f32>], window: usize) -> Vec<Option<f32>> {
+    let window = window.max(1);
+    (0..values.len())
+        .map(|idx| {
+            let slice = &values[(idx + 1).saturating_sub(window)..=idx];
+            mean(slice.iter().flatten().copied())
+        })
+        .collect()
+}
+
+/// Performance of a single match between `-1.0` and `1.0`.
+fn match_score(m: &Match) -> Option<f32> {
+    let result = match m.won()? {
+        true => 1.0,
+        false => -1.0,
+    };
+    match m.kd() {
+        Some(kd) => Some((result + (kd - 1.0).clamp(-1.0, 1.0)) / 2.0),
+        None => Some(result),
+    }
+}
+
+impl Form {
+    pub fn from_matches(matches: &Matches, options: &FormOptions) -> Form {
+        // the api returns the newest match first
+        let chronological = matches.0.iter().rev().collect::<Vec<_>>();
+        let outcomes = chronological.iter().filter_map(|m| m.won());
+        let outcomes = outcomes.collect::<Vec<_>>();
+
+        let (longest_win, longest_loss) = Streak::longest(outcomes.iter().copied());
+        let recent = &matches.0[..options.last_n.min(matches.0.len())];
+
+        let recent_outcomes = recent.iter().filter_map(|m| m.won()).collect::<Vec<_>>();
+        let last_n_win_rate = match recent_outcomes.len() {
+            0 => None,
+            len => Some(recent_outcomes.iter().filter(|&&won| won).count() as f32 / len as f32),
+        };
+
+        let (mut weighted, mut weights) = (0.0, 0.0);
+        let mut weight = 1.0;
+        for m in recent {
+            if let Some(score) = match_score(m) {
+                weighted += weight * score;
+                weights += weight;
+            }
+            weight *= options.decay;
+        }
+        let score = if weights > 0.0 {
+            weighted / weights
+        } else {
+            0.0
+        };
+
+        let kd = chronological.iter().map(|m| m.kd()).collect::<Vec<_>>();
+        let kr = chronological.iter().map(|m| m.kr()).collect::<Vec<_>>();
+
+        Form {
+            matches: matches.0.len(),
+            current_streak: Streak::current(outcomes),
+            longest_win_streak: longest_win.map_or(0, |s| s.length),
+            longest_loss_streak: longest_loss.map_or(0, |s| s.length),
+            last_n_win_rate,
+            rolling_kd: rolling(&kd, options.rolling_window),
+            rolling_kr: rolling(&kr, options.rolling_window),
+            score,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::shared::unix_time_sec;
+
+    fn played(won: bool, kd: Option<f32>, kr: Option<f32>) -> Match {
+        let mut stats = vec![("Result", u8::from(won).to_string())];
+        stats.extend(kd.map(|kd| ("K/D Ratio", kd.to_string())));
+        stats.extend(kr.map(|kr| ("K/R Ratio", kr.to_string())));
+        Match::fixture("match", unix_time_sec(0), None, stats)
+    }
+
+    #[test]
+    fn from_matches() {
+        // Newest first, as returned by the api.
+        let matches = Matches(vec![
+            played(true, Some(1.5), None),
+            played(true, None, None),
+            played(false, None, None),
+            played(false, Some(0.5), None),
+            played(true, Some(2.0), None),
+            played(true, None, Some(0.75)),
+            played(true, Some(1.0), Some(0.5)),
+        ]);
+        let options = FormOptions {
+            last_n: 4,
+            rolling_window: 2,
+            decay: 0.5,
+        };
+        let form = Form::from_matches(&matches, &options);
+
+        assert_eq!(form.matches, 7);
+        let current = Streak {
+            won: true,
+            length: 2,
+        };
+        assert_eq!(form.current_streak, Some(current));
+        assert_eq!(form.longest_win_streak, 3);
+        assert_eq!(form.longest_loss_streak, 2);
+        assert_eq!(form.last_n_win_rate, Some(0.5));
+
+        // Scores of the last 4 matches are 0.75, 1.0, -1.0 and -0.75,
+        // weighted by 1.0, 0.5, 0.25 and 0.125.
+        let expected = (0.75 + 0.5 - 0.25 - 0.09375) / 1.875;
+        assert!((form.score - expected).abs() < 1e-6);
+
+        // One entry per match, oldest first.
+        let kd = [1.0, 1.0, 2.0, 1.25, 0.5].map(Some);
+        assert_eq!(form.rolling_kd[..5], kd);
+        assert_eq!(form.rolling_kd[5..], [None, Some(1.5)]);
+        let kr = [Some(0.5), Some(0.625), Some(0.75), None, None, None, None];
+        assert_eq!(form.rolling_kr, kr);
+    }
+}
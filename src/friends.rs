@@ -0,0 +1,182 @@
+use crate::client::Client;
+use crate::rate_limit::limiter;
+use crate::room::Room;
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+pub struct FriendNode {
+    pub depth: usize,
+    /// `None` if the player was discovered but not fetched.
+    pub nickname: Option<String>,
+    /// `None` if the friends of the player were not fetched.
+    pub friends: Option<Vec<String>>,
+}
+
+impl FriendNode {
+    fn unfetched(depth: usize) -> FriendNode {
+        FriendNode {
+            depth,
+            nickname: None,
+            friends: None,
+        }
+    }
+}
+
+/// Friends of a player up to a maximum depth, keyed by player id.
+#[derive(Debug, Serialize)]
+pub struct FriendGraph {
+    pub root: String,
+    pub nodes: HashMap<String, FriendNode>,
+}
+
+impl FriendGraph {
+    fn friends(&self, player_id: &str) -> &[String] {
+        let node = self.nodes.get(player_id);
+        node.and_then(|n| n.friends.as_deref()).unwrap_or_default()
+    }
+
+    /// Friends both players have in common, only known if the friends of
+    /// both players were fetched.
+    pub fn mutual_friends(&self, player_1: &str, player_2: &str) -> Vec<&str> {
+        let friends_2 = self.friends(player_2).iter().collect::<HashSet<_>>();
+        let friends_1 = self.friends(player_1).iter();
+        let mutual = friends_1.filter(|id| friends_2.contains(id));
+        mutual.map(String::as_str).collect()
+    }
+
+    /// Groups of players connected by friendships, largest first. Edges to
+    /// players outside of the graph are ignored.
+    pub fn connected_components(&self) -> Vec<Vec<String>> {
+        let mut edges = HashMap::<&str, Vec<&str>>::new();
+        for (id, node) in self.nodes.iter() {
+            for friend in node.friends.iter().flatten() {
+                if self.nodes.contains_key(friend) {
+                    edges.entry(id).or_default().push(friend);
+                    edges.entry(friend).or_default().push(id);
+                }
+            }
+        }
+
+        let mut visited = HashSet::new();
+        let mut components = Vec::new();
+        for id in self.nodes.keys() {
+            if !visited.insert(id.as_str()) {
+                continue;
+            }
+            let mut component = Vec::new();
+            let mut queue = VecDeque::from([id.as_str()]);
+            while let Some(id) = queue.pop_front() {
+                component.push(id.to_string());
+                for &next in edges.get(id).into_iter().flatten() {
+                    if visited.insert(next) {
+                        queue.push_back(next);
+                    }
+                }
+            }
+            component.sort();
+            components.push(component);
+        }
+        components.sort_by(|a, b| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+        components
+    }
+}
+
+/// Counts how often each player was on the same team as `player_id`,
+/// most frequent teammate first.
+pub fn frequent_teammates<'a, I>(player_id: &str, rooms: I) -> Vec<(String, usize)>
+where
+    I: IntoIterator<Item = &'a Room>,
+{
+    let mut counts = HashMap::<&str, usize>::new();
+    for room in rooms {
+        let mut teams = room.teams.iter();
+        let team = teams.find(|team| team.roster.iter().any(|p| p.id == player_id));
+        let teammates = team.into_iter().flat_map(|team| team.roster.iter());
+        for teammate in teammates.filter(|p| p.id != player_id) {
+            *counts.entry(&teammate.id).or_default() += 1;
+        }
+    }
+
+    let mut counts = counts
+        .into_iter()
+        .map(|(id, count)| (id.to_string(), count))
+        .collect::<Vec<_>>();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    counts
+}
+
+impl Client {
+    /// Crawls the friends of a player breadth first, starting at `nickname`.
+    ///
+    /// Players up to `max_depth` are fetched, with at most `per_sec`
+    /// requests per second (clamped between 1 and 1000) and at most
+    /// `max_players` fetched players.
+    /// Friends of the last fetched players are added to the graph without
+    /// being fetched themselves.
+    pub async fn friend_graph(
+        &self,
+        nickname: &str,
+        max_depth: usize,
+        max_players: usize,
+        per_sec: u64,
+    ) -> reqwest::Result<FriendGraph> {
+        // Every request waits for a tick, fetching a player takes two.
+        let mut limiter = limiter(per_sec);
+        limiter.tick().await;
+        let root = self.info(nickname).await?;
+        let mut nodes = HashMap::new();
+        let mut frontier = root.friends.clone();
+        nodes.insert(
+            root.id.clone(),
+            FriendNode {
+                depth: 0,
+                nickname: Some(root.nickname),
+                friends: Some(root.friends),
+            },
+        );
+
+        let mut fetched = 1;
+        for depth in 1..=max_depth {
+            let mut next = Vec::new();
+            let mut ids = frontier;
+            ids.sort();
+            ids.dedup();
+            ids.retain(|id| !nodes.contains_key(id));
+
+            let budget = max_players.saturating_sub(fetched).min(ids.len());
+            for id in ids.split_off(budget) {
+                nodes.insert(id, FriendNode::unfetched(depth));
+            }
+
+            for id in ids {
+                limiter.tick().await;
+                let nickname = self.nickname(&id).await?.nickname;
+                limiter.tick().await;
+                let info = self.info(&nickname).await?;
+                fetched += 1;
+
+                next.extend(info.friends.iter().cloned());
+                let node = FriendNode {
+                    depth,
+                    nickname: Some(nickname),
+                    friends: Some(info.friends),
+                };
+                nodes.insert(id, node);
+            }
+            frontier = next;
+        }
+        for id in frontier {
+            nodes
+                .entry(id)
+                .or_insert_with(|| FriendNode::unfetched(max_depth + 1));
+        }
+
+        Ok(FriendGraph {
+            root: root.id,
+            nodes,
+        })
+    }
+}
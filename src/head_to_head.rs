@@ -0,0 +1,224 @@
+use crate::client::Client;
+use crate::mapping::Mapping;
+use crate::matches::{Match, Matches};
+use crate::rate_limit::limiter;
+use crate::room::Room;
+
+use std::collections::HashMap;
+
+use futures::{StreamExt, TryStreamExt};
+use serde::Serialize;
+
+const PAGE_SIZE: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum Relation {
+    Teammates,
+    Opponents,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SharedMatch {
+    pub relation: Relation,
+    pub player_1_won: Option<bool>,
+    /// The match from the history of the first player, including their stats.
+    pub player_1: Match,
+    /// The match from the history of the second player, including their stats.
+    pub player_2: Match,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct Record {
+    pub wins_together: usize,
+    pub losses_together: usize,
+    pub player_1_wins: usize,
+    pub player_2_wins: usize,
+}
+
+/// Matches two players both played in, newest first.
+#[derive(Debug, Serialize)]
+pub struct HeadToHead {
+    pub player_1: String,
+    pub player_2: String,
+    pub matches: Vec<SharedMatch>,
+}
+
+impl HeadToHead {
+    pub fn teammates(&self) -> impl Iterator<Item = &SharedMatch> {
+        let matches = self.matches.iter();
+        matches.filter(|m| m.relation == Relation::Teammates)
+    }
+    pub fn opponents(&self) -> impl Iterator<Item = &SharedMatch> {
+        let matches = self.matches.iter();
+        matches.filter(|m| m.relation == Relation::Opponents)
+    }
+    pub fn record(&self) -> Record {
+        let mut record = Record::default();
+        for m in self.matches.iter() {
+            match (m.relation, m.player_1_won) {
+                (Relation::Teammates, Some(true)) => record.wins_together += 1,
+                (Relation::Teammates, Some(false)) => record.losses_together += 1,
+                (Relation::Opponents, Some(true)) => record.player_1_wins += 1,
+                (Relation::Opponents, Some(false)) => record.player_2_wins += 1,
+                (_, None) => {}
+            }
+        }
+        record
+    }
+}
+
+fn relation(room: &Room, player_1: &str, player_2: &str) -> Option<Relation> {
+    let faction = |id: &str| {
+        let mut teams = room.teams.iter();
+        teams.position(|team| team.roster.iter().any(|p| p.id == id))
+    };
+    match faction(player_1)? == faction(player_2)? {
+        true => Some(Relation::Teammates),
+        false => Some(Relation::Opponents),
+    }
+}
+
+/// Uses the room rosters if the room could be fetched, otherwise players
+/// with the same team id are teammates.
+fn shared_match(
+    room: Option<&Room>,
+    player_1: (&str, Match),
+    player_2: (&str, Match),
+) -> SharedMatch {
+    let ((id_1, m1), (id_2, m2)) = (player_1, player_2);
+    let relation = match room.and_then(|room| relation(room, id_1, id_2)) {
+        Some(relation) => relation,
+        None if m1.team_id.is_some() && m1.team_id == m2.team_id => Relation::Teammates,
+        None => Relation::Opponents,
+    };
+    SharedMatch {
+        relation,
+        player_1_won: m1.won(),
+        player_1: m1,
+        player_2: m2,
+    }
+}
+
+impl Client {
+    async fn recent_history(
+        &self,
+        user_id: &str,
+        limit: usize,
+        mapping: &Mapping,
+    ) -> reqwest::Result<Matches> {
+        let history = self.match_history(user_id, PAGE_SIZE.min(limit.max(1)));
+        let mut matches = Matches(history.take(limit).try_collect().await?);
+        matches.map_stats(mapping);
+        Ok(matches)
+    }
+
+    /// Compares the last `limit` matches of both players and classifies
+    /// every match they played together using the room rosters.
+    ///
+    /// Rooms are fetched with at most `per_sec` requests per second, clamped
+    /// between 1 and 1000. If a room can't be fetched, players with the same
+    /// team id are teammates.
+    pub async fn head_to_head(
+        &self,
+        player_1: &str,
+        player_2: &str,
+        limit: usize,
+        mapping: &Mapping,
+        per_sec: u64,
+    ) -> reqwest::Result<HeadToHead> {
+        let (history_1, history_2) = futures::try_join!(
+            self.recent_history(player_1, limit, mapping),
+            self.recent_history(player_2, limit, mapping),
+        )?;
+
+        let mut history_2 = history_2
+            .0
+            .into_iter()
+            .map(|m| (m.match_id.clone(), m))
+            .collect::<HashMap<_, _>>();
+
+        let mut limiter = limiter(per_sec);
+        let mut matches = Vec::new();
+        for m1 in history_1.0 {
+            let m2 = match history_2.remove(&m1.match_id) {
+                Some(m2) => m2,
+                None => continue,
+            };
+            limiter.tick().await;
+            let room = self.room(&m1.match_id).await.ok();
+            matches.push(shared_match(room.as_ref(), (player_1, m1), (player_2, m2)));
+        }
+
+        Ok(HeadToHead {
+            player_1: player_1.to_string(),
+            player_2: player_2.to_string(),
+            matches,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::unix_time_sec;
+
+    fn played(team_id: Option<&str>, won: bool) -> Match {
+        let stats = [("Result", u8::from(won))];
+        Match {
+            team_id: team_id.map(str::to_string),
+            ..Match::fixture("room", unix_time_sec(0), None, stats)
+        }
+    }
+
+    #[test]
+    fn classifies_and_counts_shared_matches() {
+        let room = Room::fixture("room", &["a", "b"], &["c", "d"]);
+        let together = shared_match(
+            Some(&room),
+            ("a", played(Some("t1"), true)),
+            ("b", played(Some("t1"), true)),
+        );
+        // The rosters take precedence over the team ids.
+        let against = shared_match(
+            Some(&room),
+            ("a", played(Some("t1"), false)),
+            ("c", played(Some("t1"), true)),
+        );
+        // Without a room the team ids decide.
+        let fallback_together = shared_match(
+            None,
+            ("a", played(Some("t1"), false)),
+            ("b", played(Some("t1"), false)),
+        );
+        let fallback_against = shared_match(
+            None,
+            ("a", played(Some("t1"), true)),
+            ("c", played(Some("t2"), false)),
+        );
+        let unknown_teams =
+            shared_match(None, ("a", played(None, true)), ("b", played(None, true)));
+
+        let relations = [&together, &against, &fallback_together, &fallback_against];
+        let relations = relations.map(|m| m.relation);
+        use Relation::{Opponents, Teammates};
+        assert_eq!(relations, [Teammates, Opponents, Teammates, Opponents]);
+        assert_eq!(unknown_teams.relation, Opponents);
+
+        let h2h = HeadToHead {
+            player_1: "a".to_string(),
+            player_2: "b".to_string(),
+            matches: vec![
+                together,
+                against,
+                fallback_together,
+                fallback_against,
+                unknown_teams,
+            ],
+        };
+        assert_eq!(h2h.teammates().count(), 2);
+        assert_eq!(h2h.opponents().count(), 3);
+        let record = h2h.record();
+        assert_eq!((record.wins_together, record.losses_together), (1, 1));
+        assert_eq!((record.player_1_wins, record.player_2_wins), (2, 1));
+    }
+}
@@ -0,0 +1,318 @@
+use crate::client::Client;
+use crate::data_api::{HistoryMatch, Hub, HubMember};
+use crate::live::MatchStatus;
+use crate::room::MatchResult;
+use crate::shared::{parse_time, Items, Response};
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Local};
+use futures::stream::{self, Stream, TryStreamExt};
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize)]
+struct Hub_ {
+    #[serde(alias = "guid")]
+    id: String,
+    name: String,
+    avatar: Option<String>,
+    #[serde(rename = "coverImage")]
+    cover_image: Option<String>,
+    description: Option<String>,
+    game: Option<String>,
+    region: Option<String>,
+    #[serde(rename = "organizerGuid")]
+    organizer_id: Option<String>,
+    #[serde(rename = "membersCount")]
+    members: Option<u32>,
+    #[serde(rename = "minSkillLevel")]
+    min_skill_level: Option<u8>,
+    #[serde(rename = "maxSkillLevel")]
+    max_skill_level: Option<u8>,
+}
+
+impl From<Hub_> for Hub {
+    fn from(hub: Hub_) -> Hub {
+        Hub {
+            faceit_url: Some(format!("https://www.faceit.com/en/hub/{}", hub.id)),
+            id: hub.id,
+            name: hub.name,
+            avatar: hub.avatar,
+            cover_image: hub.cover_image,
+            description: hub.description,
+            game_id: hub.game,
+            region: hub.region,
+            organizer_id: hub.organizer_id,
+            players_joined: hub.members,
+            min_skill_level: hub.min_skill_level,
+            max_skill_level: hub.max_skill_level,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct User_ {
+    #[serde(alias = "guid")]
+    id: String,
+    nickname: String,
+    avatar: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Member_ {
+    user: User_,
+    #[serde(default)]
+    roles: Vec<String>,
+}
+
+impl From<Member_> for HubMember {
+    fn from(member: Member_) -> HubMember {
+        HubMember {
+            id: member.user.id,
+            nickname: member.user.nickname,
+            avatar: member.user.avatar,
+            roles: member.roles,
+        }
+    }
+}
+
+/// A role members of a hub can have, see [`HubMember::roles`].
+#[derive(Deserialize, Serialize, Debug)]
+pub struct HubRole {
+    #[serde(rename = "role_id")]
+    pub id: String,
+    pub name: String,
+    pub color: Option<String>,
+    /// Roles with a higher ranking have more permissions.
+    #[serde(default)]
+    pub ranking: u32,
+}
+
+#[derive(Deserialize)]
+struct Player_ {
+    id: String,
+}
+
+#[derive(Deserialize)]
+struct Team_ {
+    #[serde(default)]
+    roster: Vec<Player_>,
+}
+
+#[derive(Deserialize)]
+struct HubMatch_ {
+    id: String,
+    region: Option<String>,
+    status: String,
+    #[serde(rename = "startedAt")]
+    started_at: Option<String>,
+    #[serde(rename = "finishedAt")]
+    finished_at: Option<String>,
+    #[serde(default)]
+    teams: HashMap<String, Team_>,
+    #[serde(default)]
+    results: Vec<MatchResult>,
+}
+
+impl HubMatch_ {
+    fn into_history(self, hub_id: &str) -> HistoryMatch {
+        let players = self.teams.into_values().flat_map(|team| team.roster);
+
+        HistoryMatch {
+            match_id: self.id,
+            region: self.region,
+            game_mode: None,
+            competition_id: Some(hub_id.to_string()),
+            competition_name: None,
+            competition_type: Some("hub".to_string()),
+            organizer_id: None,
+            status: MatchStatus::parse(&self.status),
+            started_at: parse_time(self.started_at.as_deref()),
+            finished_at: parse_time(self.finished_at.as_deref()),
+            result: self.results.into_iter().last(),
+            players: players.map(|p| p.id).collect(),
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct Leaderboard {
+    pub id: String,
+    pub name: String,
+    pub status: Option<String>,
+    #[serde(default)]
+    pub start_date: Option<DateTime<Local>>,
+    #[serde(default)]
+    pub end_date: Option<DateTime<Local>>,
+}
+
+#[derive(Deserialize)]
+struct LeaderboardItem_ {
+    leaderboard: Leaderboard,
+}
+
+#[derive(Deserialize)]
+struct RankedPlayer_ {
+    #[serde(alias = "user_id")]
+    id: String,
+    nickname: String,
+}
+
+#[derive(Deserialize)]
+struct Ranking_ {
+    position: u32,
+    player: RankedPlayer_,
+    #[serde(default)]
+    points: i32,
+    #[serde(default)]
+    played: u32,
+    #[serde(default)]
+    won: u32,
+    #[serde(default)]
+    lost: u32,
+}
+
+#[derive(Deserialize)]
+struct Rankings_ {
+    rankings: Vec<Ranking_>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct LeaderboardEntry {
+    pub position: u32,
+    pub player_id: String,
+    pub nickname: String,
+    pub points: i32,
+    pub played: u32,
+    pub won: u32,
+    pub lost: u32,
+}
+
+impl From<Ranking_> for LeaderboardEntry {
+    fn from(ranking: Ranking_) -> LeaderboardEntry {
+        LeaderboardEntry {
+            position: ranking.position,
+            player_id: ranking.player.id,
+            nickname: ranking.player.nickname,
+            points: ranking.points,
+            played: ranking.played,
+            won: ranking.won,
+            lost: ranking.lost,
+        }
+    }
+}
+
+impl Client {
+    /// - `https://api.faceit.com/hubs/v1/hub/{HUB_ID}`
+    pub async fn hub(&self, hub_id: &str) -> reqwest::Result<Hub> {
+        const PREFIX: &str = "https://api.faceit.com/hubs/v1/hub";
+
+        let url = format!("{}/{}", PREFIX, hub_id);
+
+        let resp = self.get_json::<Response<Hub_>>(&url, &[]).await?;
+        Ok(resp.payload.into())
+    }
+
+    /// - `https://api.faceit.com/hubs/v1/hub/{HUB_ID}/membership`
+    pub async fn hub_members(
+        &self,
+        hub_id: &str,
+        offset: usize,
+        limit: usize,
+    ) -> reqwest::Result<Vec<HubMember>> {
+        const PREFIX: &str = "https://api.faceit.com/hubs/v1/hub";
+
+        let (offset, limit) = (offset.to_string(), limit.to_string());
+        let query = [("offset", offset.as_str()), ("limit", &limit)];
+        let url = format!("{}/{}/membership", PREFIX, hub_id);
+
+        let resp = self.get_json::<Response<Items<Member_>>>(&url, &query);
+        let members = resp.await?.payload.items.into_iter();
+        Ok(members.map(HubMember::from).collect())
+    }
+
+    /// - `https://api.faceit.com/hubs/v1/hub/{HUB_ID}/role`
+    pub async fn hub_roles(&self, hub_id: &str) -> reqwest::Result<Vec<HubRole>> {
+        const PREFIX: &str = "https://api.faceit.com/hubs/v1/hub";
+
+        let url = format!("{}/{}/role", PREFIX, hub_id);
+
+        Ok(self.get_json::<Response<_>>(&url, &[]).await?.payload)
+    }
+
+    /// Finished matches of the hub, newest first. The match ids are room ids,
+    /// see [`Client::room`] and [`Client::room_stats`].
+    ///
+    /// - `https://api.faceit.com/match/v1/matches/list`
+    pub async fn hub_matches(
+        &self,
+        hub_id: &str,
+        offset: usize,
+        limit: usize,
+    ) -> reqwest::Result<Vec<HistoryMatch>> {
+        const API: &str = "https://api.faceit.com/match/v1/matches/list";
+
+        let (offset, limit) = (offset.to_string(), limit.to_string());
+        let query = [
+            ("entityId", hub_id),
+            ("entityType", "hub"),
+            ("state", "FINISHED"),
+            ("offset", &offset),
+            ("limit", &limit),
+        ];
+
+        let resp = self
+            .get_json::<Response<Vec<HubMatch_>>>(API, &query)
+            .await?;
+        let matches = resp.payload.into_iter();
+        Ok(matches.map(|m| m.into_history(hub_id)).collect())
+    }
+
+    /// Pages through every finished match of the hub, newest first,
+    /// until the api returns an empty page.
+    pub fn hub_match_history<'a>(
+        &'a self,
+        hub_id: &'a str,
+        page_size: usize,
+    ) -> impl Stream<Item = reqwest::Result<HistoryMatch>> + 'a {
+        let pages = stream::try_unfold(0, move |offset| async move {
+            let matches = self.hub_matches(hub_id, offset, page_size).await?;
+            if matches.is_empty() {
+                return Ok(None);
+            }
+            let next = offset + matches.len();
+            let matches = stream::iter(matches.into_iter().map(Ok));
+            Ok(Some((matches, next)))
+        });
+        pages.try_flatten()
+    }
+
+    /// - `https://api.faceit.com/leaderboard/v1/leaderboard/hub/{HUB_ID}`
+    pub async fn hub_leaderboards(&self, hub_id: &str) -> reqwest::Result<Vec<Leaderboard>> {
+        const PREFIX: &str = "https://api.faceit.com/leaderboard/v1/leaderboard/hub";
+
+        let url = format!("{}/{}", PREFIX, hub_id);
+
+        let resp = self.get_json::<Response<Vec<LeaderboardItem_>>>(&url, &[]);
+        let items = resp.await?.payload.into_iter();
+        Ok(items.map(|item| item.leaderboard).collect())
+    }
+
+    /// - `https://api.faceit.com/leaderboard/v1/ranking/leaderboard/{LEADERBOARD_ID}`
+    pub async fn leaderboard(
+        &self,
+        leaderboard_id: &str,
+        offset: usize,
+        limit: usize,
+    ) -> reqwest::Result<Vec<LeaderboardEntry>> {
+        const PREFIX: &str = "https://api.faceit.com/leaderboard/v1/ranking/leaderboard";
+
+        let (offset, limit) = (offset.to_string(), limit.to_string());
+        let query = [("offset", offset.as_str()), ("limit", &limit)];
+        let url = format!("{}/{}", PREFIX, leaderboard_id);
+
+        let resp = self.get_json::<Response<Rankings_>>(&url, &query).await?;
+        let rankings = resp.payload.rankings.into_iter();
+        Ok(rankings.map(LeaderboardEntry::from).collect())
+    }
+}
@@ -2,15 +2,18 @@
 
 mod client;
 pub use client::Client;
+mod map_performance;
+pub use map_performance::{MapPerformance, MapRecord, Window};
 mod mapping;
 pub use mapping::{MapStats, Mapping};
 mod matches;
 pub use matches::{Match, Matches};
 mod nickname;
+pub use nickname::Nickname;
 mod rate_limit;
 pub use rate_limit::{rate_limit, RateLimitIter};
 mod room;
-pub use room::Room;
+pub use room::{MatchResult, Room, Server, SummaryResults, Team, Teams};
 mod search;
 pub use search::Search;
 mod shared;
@@ -19,8 +22,65 @@ pub use stats::Stats;
 mod humanize;
 pub use humanize::*;
 mod democracy;
-pub use democracy::Democracy;
+pub use democracy::{Democracy, PickBan};
 mod player_info;
+pub use player_info::PlayerInfo;
 mod room_stats;
-pub use room_stats::{MatchStats, RoomStats};
+pub use room_stats::{MatchStats, PlayerStats, RoomStats, TeamResult, TeamStats};
 mod types;
+mod scouting;
+pub use scouting::{PlayerReport, ScoutingReport, TeamReport};
+mod elo_history;
+pub use elo_history::{EloHistory, EloPoint, Streak};
+mod form;
+pub use form::{Form, FormOptions};
+mod rating;
+pub use rating::{PlayerRating, Rating, RatingSummary};
+mod party;
+pub use party::{FactionParties, Party, PartyAnalysis};
+mod expected;
+pub use expected::{win_probability, Expectation, FactionExpectation};
+mod head_to_head;
+pub use head_to_head::{HeadToHead, Record, Relation, SharedMatch};
+mod friends;
+pub use friends::{frequent_teammates, FriendGraph, FriendNode};
+mod smurf;
+pub use smurf::{Category, Signal, SmurfConfig, SmurfReport, Threshold};
+mod reliability;
+pub use reliability::{PlayerReliability, Reliability};
+mod veto_analysis;
+pub use veto_analysis::{PoolTendencies, VetoAnalysis, VetoTendency};
+mod servers;
+pub use servers::{LocationRecord, PlayerLocations, ServerStats};
+mod queue_time;
+pub use queue_time::{Distribution, QueueSample, QueueTimes};
+mod schedule;
+pub use schedule::{Bucket, Schedule};
+mod ranking;
+pub use ranking::{rank_lobby, Cohort, Metric, PlayerRanking, PlayerSnapshot, Ranks};
+mod series;
+pub use series::{Series, SeriesMap};
+mod comparison;
+pub use comparison::{ComparedPlayer, Comparison, Difference};
+mod watcher;
+pub use watcher::MatchFinished;
+mod live;
+pub use live::{LiveEvent, MatchStatus, PollIntervals, RoomStatus, Score};
+#[cfg(feature = "webhook")]
+mod webhook;
+#[cfg(feature = "webhook")]
+pub use webhook::{
+    Entity, EventKind, MatchPayload, WebhookError, WebhookEvent, WebhookHandler, WebhookPlayer,
+    WebhookTeam,
+};
+mod data_api;
+pub use data_api::{
+    Championship, DataClient, DataMatch, DataPlayer, DataProfile, DataTeam, HistoryMatch, Hub,
+    HubMember, LifetimeStats, RankingEntry, Segment,
+};
+mod session;
+pub use session::{Session, SessionError};
+mod hub;
+pub use hub::{HubRole, Leaderboard, LeaderboardEntry};
+mod championship;
+pub use championship::{Bracket, BracketMatch, BracketRound, BracketTeam};
@@ -0,0 +1,449 @@
+use crate::client::Client;
+use crate::room::{MatchResult, Server};
+
+use std::collections::VecDeque;
+use std::future::Future;
+use std::time::Duration;
+
+use futures::stream::{self, Stream};
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Debug, Default)]
+struct VotingMap {
+    #[serde(default)]
+    pick: Vec<String>,
+}
+
+#[derive(Deserialize, Debug, Default)]
+struct Voting {
+    #[serde(default)]
+    map: VotingMap,
+}
+
+#[derive(Deserialize, Debug)]
+struct ClientCustom {
+    #[serde(rename = "team1_score")]
+    team_1_score: Option<u8>,
+    #[serde(rename = "team2_score")]
+    team_2_score: Option<u8>,
+    map: Option<String>,
+    server: Option<Server>,
+}
+
+/// Only the parts of the room that change while it is played, everything
+/// else is missing until the match is configured or finished.
+#[derive(Deserialize, Debug)]
+struct Payload {
+    id: String,
+    status: String,
+    #[serde(default)]
+    voting: Option<Voting>,
+    #[serde(rename = "clientCustom")]
+    client_custom: Option<ClientCustom>,
+    #[serde(default)]
+    results: Vec<MatchResult>,
+}
+
+#[derive(Deserialize, Debug)]
+struct Response {
+    payload: Payload,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub enum MatchStatus {
+    CheckIn,
+    Voting,
+    Configuring,
+    Ready,
+    Ongoing,
+    Finished,
+    Cancelled,
+    Other(String),
+}
+
+impl MatchStatus {
+    pub fn parse(status: &str) -> MatchStatus {
+        match status.to_ascii_uppercase().as_str() {
+            "CHECK_IN" => MatchStatus::CheckIn,
+            "VOTING" => MatchStatus::Voting,
+            "CONFIGURING" => MatchStatus::Configuring,
+            "READY" => MatchStatus::Ready,
+            "ONGOING" => MatchStatus::Ongoing,
+            "FINISHED" => MatchStatus::Finished,
+            "CANCELLED" => MatchStatus::Cancelled,
+            _ => MatchStatus::Other(status.to_string()),
+        }
+    }
+    pub fn is_over(&self) -> bool {
+        matches!(self, MatchStatus::Finished | MatchStatus::Cancelled)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Score {
+    pub map: Option<String>,
+    pub faction_1: u8,
+    pub faction_2: u8,
+}
+
+/// The state of a room that may not have finished yet.
+#[derive(Debug, Clone, Serialize)]
+pub struct RoomStatus {
+    pub id: String,
+    pub status: MatchStatus,
+    pub maps: Vec<String>,
+    pub server: Option<Server>,
+    pub score: Option<Score>,
+    pub results: Vec<MatchResult>,
+}
+
+impl From<Response> for RoomStatus {
+    fn from(response: Response) -> RoomStatus {
+        let pl = response.payload;
+        let (server, score) = match pl.client_custom {
+            Some(custom) => {
+                let score = match (custom.team_1_score, custom.team_2_score) {
+                    (Some(faction_1), Some(faction_2)) => Some(Score {
+                        map: custom.map,
+                        faction_1,
+                        faction_2,
+                    }),
+                    _ => None,
+                };
+                (custom.server, score)
+            }
+            None => (None, None),
+        };
+
+        RoomStatus {
+            id: pl.id,
+            status: MatchStatus::parse(&pl.status),
+            maps: pl.voting.unwrap_or_default().map.pick,
+            server,
+            score,
+            results: pl.results,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub enum LiveEvent {
+    StatusChanged {
+        from: Option<MatchStatus>,
+        to: MatchStatus,
+    },
+    VotingStarted,
+    MapPicked(String),
+    ServerReady(Server),
+    Live,
+    ScoreUpdate(Score),
+    Finished(Vec<MatchResult>),
+    Cancelled,
+}
+
+/// How long to wait between two polls, depending on the last known status.
+#[derive(Debug, Clone)]
+pub struct PollIntervals {
+    /// Check in and voting, both take less than a minute.
+    pub voting: Duration,
+    /// Configuring and ready, until the server is up and everyone connected.
+    pub configuring: Duration,
+    /// A round takes about two minutes.
+    pub ongoing: Duration,
+    pub other: Duration,
+}
+
+impl Default for PollIntervals {
+    fn default() -> PollIntervals {
+        PollIntervals {
+            voting: Duration::from_secs(5),
+            configuring: Duration::from_secs(10),
+            ongoing: Duration::from_secs(30),
+            other: Duration::from_secs(15),
+        }
+    }
+}
+
+impl PollIntervals {
+    pub fn get(&self, status: &MatchStatus) -> Duration {
+        match status {
+            MatchStatus::CheckIn | MatchStatus::Voting => self.voting,
+            MatchStatus::Configuring | MatchStatus::Ready => self.configuring,
+            MatchStatus::Ongoing => self.ongoing,
+            _ => self.other,
+        }
+    }
+}
+
+/// The events between two polls of the same room, in the order they happened.
+fn diff(prev: Option<&RoomStatus>, next: &RoomStatus) -> Vec<LiveEvent> {
+    let mut events = Vec::new();
+    let status_changed = prev.is_none_or(|prev| prev.status != next.status);
+
+    if status_changed {
+        events.push(LiveEvent::StatusChanged {
+            from: prev.map(|prev| prev.status.clone()),
+            to: next.status.clone(),
+        });
+        if next.status == MatchStatus::Voting {
+            events.push(LiveEvent::VotingStarted);
+        }
+    }
+
+    let picked = prev.map(|prev| prev.maps.as_slice()).unwrap_or_default();
+    for map in next.maps.iter().filter(|map| !picked.contains(map)) {
+        events.push(LiveEvent::MapPicked(map.clone()));
+    }
+
+    let had_server = prev.is_some_and(|prev| prev.server.is_some());
+    if let (false, Some(server)) = (had_server, &next.server) {
+        events.push(LiveEvent::ServerReady(server.clone()));
+    }
+
+    if status_changed && next.status == MatchStatus::Ongoing {
+        events.push(LiveEvent::Live);
+    }
+
+    let prev_score = prev.and_then(|prev| prev.score.as_ref());
+    if let Some(score) = next.score.as_ref().filter(|&s| Some(s) != prev_score) {
+        events.push(LiveEvent::ScoreUpdate(score.clone()));
+    }
+
+    if status_changed {
+        match next.status {
+            MatchStatus::Finished => events.push(LiveEvent::Finished(next.results.clone())),
+            MatchStatus::Cancelled => events.push(LiveEvent::Cancelled),
+            _ => {}
+        }
+    }
+
+    events
+}
+
+struct State<F, E, P> {
+    fetch: F,
+    is_fatal: P,
+    intervals: PollIntervals,
+    last: Option<RoomStatus>,
+    pending: VecDeque<Result<LiveEvent, E>>,
+    failed: bool,
+    done: bool,
+}
+
+/// Polls `fetch` until the room is finished or cancelled and yields every
+/// change between two polls.
+///
+/// Errors are yielded and polling continues after `intervals.other`,
+/// unless `is_fatal` returns `true` for the error, which ends the stream.
+fn poll_status<F, Fut, E, P>(
+    fetch: F,
+    intervals: PollIntervals,
+    is_fatal: P,
+) -> impl Stream<Item = Result<LiveEvent, E>>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<RoomStatus, E>>,
+    P: Fn(&E) -> bool,
+{
+    let state = State {
+        fetch,
+        is_fatal,
+        intervals,
+        last: None,
+        pending: VecDeque::new(),
+        failed: false,
+        done: false,
+    };
+    stream::unfold(state, |mut state| async move {
+        loop {
+            if let Some(event) = state.pending.pop_front() {
+                return Some((event, state));
+            }
+            if state.done {
+                return None;
+            }
+            match (&state.last, state.failed) {
+                (Some(last), _) if last.status.is_over() => return None,
+                (_, true) => tokio::time::sleep(state.intervals.other).await,
+                (Some(last), false) => tokio::time::sleep(state.intervals.get(&last.status)).await,
+                (None, false) => {}
+            }
+            match (state.fetch)().await {
+                Ok(next) => {
+                    let events = diff(state.last.as_ref(), &next);
+                    state.pending.extend(events.into_iter().map(Ok));
+                    state.last = Some(next);
+                    state.failed = false;
+                }
+                Err(err) => {
+                    state.done = (state.is_fatal)(&err);
+                    state.failed = true;
+                    state.pending.push_back(Err(err));
+                }
+            }
+        }
+    })
+}
+
+/// Client errors other than rate limiting won't go away by polling again,
+/// e.g. a `404` for an unknown room id.
+fn is_client_error(err: &reqwest::Error) -> bool {
+    let status = err.status();
+    status.is_some_and(|s| s.is_client_error() && s != StatusCode::TOO_MANY_REQUESTS)
+}
+
+impl Client {
+    /// - `https://api.faceit.com/match/v2/match/{MATCH_ID}`
+    ///
+    /// Only the parts of the room that change while it is played,
+    /// see [`Client::room`] for everything else.
+    pub async fn room_status(&self, room_id: &str) -> reqwest::Result<RoomStatus> {
+        const PREFIX: &str = "https://api.faceit.com/match/v2/match";
+
+        let url = format!("{}/{}", PREFIX, room_id);
+
+        Ok(self.get_json::<Response>(&url, &[]).await?.into())
+    }
+
+    /// Polls the room until it is finished or cancelled and yields its state changes.
+    ///
+    /// Ends early after a client error like `404`, other errors are yielded
+    /// and polling is retried.
+    pub fn live<'a>(
+        &'a self,
+        room_id: &'a str,
+        intervals: PollIntervals,
+    ) -> impl Stream<Item = reqwest::Result<LiveEvent>> + 'a {
+        poll_status(
+            move || self.room_status(room_id),
+            intervals,
+            is_client_error,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use futures::StreamExt;
+
+    fn status(status: MatchStatus, maps: &[&str], score: Option<(u8, u8)>) -> RoomStatus {
+        let ongoing = matches!(status, MatchStatus::Ongoing | MatchStatus::Finished);
+        RoomStatus {
+            id: "room".to_string(),
+            status,
+            maps: maps.iter().map(|map| map.to_string()).collect(),
+            server: ongoing.then(|| Server {
+                country: "DE".to_string(),
+                ip: "127.0.0.1".to_string(),
+                port: "27015".to_string(),
+            }),
+            score: score.map(|(faction_1, faction_2)| Score {
+                map: maps.first().map(|map| map.to_string()),
+                faction_1,
+                faction_2,
+            }),
+            results: Vec::new(),
+        }
+    }
+
+    fn intervals(other: Duration) -> PollIntervals {
+        PollIntervals {
+            voting: Duration::from_millis(1),
+            configuring: Duration::from_millis(1),
+            ongoing: Duration::from_millis(1),
+            other,
+        }
+    }
+
+    #[tokio::test]
+    async fn waits_after_errors() {
+        let mut states = vec![
+            Err("unreachable"),
+            Err("unreachable"),
+            Ok(status(MatchStatus::Finished, &["de_mirage"], Some((13, 9)))),
+        ]
+        .into_iter();
+
+        let start = tokio::time::Instant::now();
+        let fetch = move || futures::future::ready(states.next().expect("polled after finish"));
+        let intervals = intervals(Duration::from_millis(50));
+        let events = poll_status(fetch, intervals, |_| false);
+        let events = events.collect::<Vec<_>>().await;
+
+        assert!(start.elapsed() >= Duration::from_millis(100));
+        assert_eq!(events.iter().filter(|e| e.is_err()).count(), 2);
+        assert!(matches!(events.last(), Some(Ok(LiveEvent::Finished(_)))));
+    }
+
+    #[tokio::test]
+    async fn ends_on_fatal_errors() {
+        let polls = std::cell::Cell::new(0);
+        let fetch = || {
+            polls.set(polls.get() + 1);
+            futures::future::ready(Err::<RoomStatus, _>("not found"))
+        };
+        let intervals = intervals(Duration::from_millis(1));
+        let events = poll_status(fetch, intervals, |&err| err == "not found");
+        let events = events.collect::<Vec<_>>().await;
+
+        assert!(matches!(events.as_slice(), [Err("not found")]));
+        assert_eq!(polls.get(), 1);
+    }
+
+    #[tokio::test]
+    async fn steps_through_states() {
+        let mut states = vec![
+            Ok(status(MatchStatus::Voting, &[], None)),
+            Ok(status(MatchStatus::Configuring, &["de_mirage"], None)),
+            Err("timeout"),
+            Ok(status(MatchStatus::Ongoing, &["de_mirage"], Some((0, 0)))),
+            Ok(status(MatchStatus::Ongoing, &["de_mirage"], Some((0, 0)))),
+            Ok(status(MatchStatus::Ongoing, &["de_mirage"], Some((3, 1)))),
+            Ok(status(MatchStatus::Finished, &["de_mirage"], Some((13, 9)))),
+        ]
+        .into_iter();
+
+        let fetch = move || futures::future::ready(states.next().expect("polled after finish"));
+        let intervals = intervals(Duration::from_millis(1));
+        let events = poll_status(fetch, intervals, |_| false)
+            .collect::<Vec<_>>()
+            .await;
+
+        let names = events
+            .iter()
+            .map(|event| match event {
+                Ok(LiveEvent::StatusChanged { to, .. }) => format!("status {:?}", to),
+                Ok(LiveEvent::VotingStarted) => "voting".to_string(),
+                Ok(LiveEvent::MapPicked(map)) => format!("picked {}", map),
+                Ok(LiveEvent::ServerReady(server)) => format!("server {}", server.ip),
+                Ok(LiveEvent::Live) => "live".to_string(),
+                Ok(LiveEvent::ScoreUpdate(s)) => format!("score {}:{}", s.faction_1, s.faction_2),
+                Ok(LiveEvent::Finished(_)) => "finished".to_string(),
+                Ok(LiveEvent::Cancelled) => "cancelled".to_string(),
+                Err(err) => format!("error {}", err),
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            names,
+            [
+                "status Voting",
+                "voting",
+                "status Configuring",
+                "picked de_mirage",
+                "error timeout",
+                "status Ongoing",
+                "server 127.0.0.1",
+                "live",
+                "score 0:0",
+                "score 3:1",
+                "status Finished",
+                "score 13:9",
+                "finished",
+            ]
+        );
+    }
+}
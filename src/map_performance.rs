@@ -0,0 +1,171 @@
+use crate::matches::{Match, Matches};
+use crate::shared::mean;
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Local};
+use serde::Serialize;
+
+/// Selects which matches of a history are taken into account.
+#[derive(Debug, Clone, Copy)]
+pub enum Window {
+    All,
+    /// The `n` most recent matches.
+    Last(usize),
+    /// Every match played at or after the given time.
+    Since(DateTime<Local>),
+}
+
+impl Window {
+    /// Expects the matches newest first, as returned by the api.
+    pub(crate) fn select<'a, T>(&self, matches: &'a [(&'a Match, T)]) -> &'a [(&'a Match, T)] {
+        match *self {
+            Window::All => matches,
+            Window::Last(n) => &matches[..n.min(matches.len())],
+            Window::Since(since) => {
+                let end = matches.iter().take_while(|(m, _)| m.date >= since).count();
+                &matches[..end]
+            }
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct MapRecord {
+    pub map: String,
+    pub games: usize,
+    pub wins: usize,
+    pub win_rate: f32,
+    pub kd: Option<f32>,
+    pub adr: Option<f32>,
+    pub headshot_pct: Option<f32>,
+    /// Sum of the elo gained and lost on this map.
+    pub elo_delta: i32,
+}
+
+#[derive(Default)]
+struct Accumulator {
+    games: usize,
+    wins: usize,
+    kd: Vec<f32>,
+    adr: Vec<f32>,
+    headshot_pct: Vec<f32>,
+    elo_delta: i32,
+}
+
+impl Accumulator {
+    fn add(&mut self, m: &Match, elo_delta: Option<i32>) {
+        self.games += 1;
+        self.wins += usize::from(m.won() == Some(true));
+        self.kd.extend(m.kd());
+        self.adr.extend(m.adr());
+        self.headshot_pct.extend(m.headshot_pct());
+        self.elo_delta += elo_delta.unwrap_or_default();
+    }
+
+    fn finish(self, map: String) -> MapRecord {
+        MapRecord {
+            map,
+            games: self.games,
+            wins: self.wins,
+            win_rate: self.wins as f32 / self.games as f32,
+            kd: mean(self.kd),
+            adr: mean(self.adr),
+            headshot_pct: mean(self.headshot_pct),
+            elo_delta: self.elo_delta,
+        }
+    }
+}
+
+/// Per-map records of a player, most played map first.
+///
+/// Expects the stats of the matches to be mapped, see [`Matches::map_stats`].
+#[derive(Debug, Serialize)]
+#[serde(transparent)]
+pub struct MapPerformance(pub Vec<MapRecord>);
+
+impl MapPerformance {
+    pub fn from_matches(matches: &Matches, window: Window) -> MapPerformance {
+        let deltas = matches.elo_deltas();
+        let history = matches.0.iter().zip(deltas).collect::<Vec<_>>();
+
+        let mut maps = HashMap::<&str, Accumulator>::new();
+        for (m, delta) in window.select(&history) {
+            if let Some(map) = m.map() {
+                maps.entry(map).or_default().add(m, *delta);
+            }
+        }
+
+        let mut records = maps
+            .into_iter()
+            .map(|(map, acc)| acc.finish(map.to_string()))
+            .collect::<Vec<_>>();
+        records.sort_by(|a, b| b.games.cmp(&a.games).then_with(|| a.map.cmp(&b.map)));
+        MapPerformance(records)
+    }
+
+    pub fn get(&self, map: &str) -> Option<&MapRecord> {
+        self.0.iter().find(|record| record.map == map)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::unix_time_sec;
+
+    fn played(day: u64, map: &str, won: bool, kd: Option<f32>, elo: u16) -> Match {
+        let result = u8::from(won).to_string();
+        let mut stats = vec![("Map", map.to_string()), ("Result", result)];
+        stats.extend(kd.map(|kd| ("K/D Ratio", kd.to_string())));
+        let date = unix_time_sec(day * 24 * 60 * 60);
+        Match::fixture(&format!("match-{}", day), date, Some(elo), stats)
+    }
+
+    fn history() -> Matches {
+        Matches(vec![
+            played(5, "de_dust2", true, Some(1.5), 1050),
+            played(4, "de_mirage", false, Some(0.5), 1025),
+            played(3, "de_dust2", true, Some(1.0), 1050),
+            played(2, "de_dust2", false, None, 1025),
+            played(1, "de_mirage", true, Some(2.0), 1050),
+        ])
+    }
+
+    #[test]
+    fn aggregates_per_map() {
+        let maps = MapPerformance::from_matches(&history(), Window::All);
+        let names = maps.0.iter().map(|r| r.map.as_str()).collect::<Vec<_>>();
+        assert_eq!(names, ["de_dust2", "de_mirage"]);
+
+        let dust2 = maps.get("de_dust2").unwrap();
+        assert_eq!((dust2.games, dust2.wins), (3, 2));
+        assert!((dust2.win_rate - 2.0 / 3.0).abs() < 1e-6);
+        assert_eq!(dust2.kd, Some(1.25));
+        assert_eq!(dust2.elo_delta, 25);
+
+        // The delta of the oldest match is unknown.
+        let mirage = maps.get("de_mirage").unwrap();
+        assert_eq!((mirage.games, mirage.wins), (2, 1));
+        assert_eq!(mirage.elo_delta, -25);
+        assert!(maps.get("de_nuke").is_none());
+    }
+
+    #[test]
+    fn selects_window() {
+        let maps = MapPerformance::from_matches(&history(), Window::Last(2));
+        let games = maps.0.iter().map(|r| (r.map.as_str(), r.games));
+        assert_eq!(
+            games.collect::<Vec<_>>(),
+            [("de_dust2", 1), ("de_mirage", 1)]
+        );
+
+        let since = unix_time_sec(3 * 24 * 60 * 60);
+        let maps = MapPerformance::from_matches(&history(), Window::Since(since));
+        let games = maps.0.iter().map(|r| (r.map.as_str(), r.games));
+        assert_eq!(
+            games.collect::<Vec<_>>(),
+            [("de_dust2", 2), ("de_mirage", 1)]
+        );
+    }
+}
@@ -1,10 +1,12 @@
 use crate::client::Client;
 use crate::mapping::{MapStats, Mapping};
-use crate::shared::unix_time_ms;
+use crate::shared::{parse_stat, unix_time_ms};
 
 use std::collections::HashMap;
+use std::str::FromStr;
 
 use chrono::{DateTime, Local};
+use futures::stream::{self, Stream, TryStreamExt};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
@@ -56,7 +58,8 @@ pub struct Match {
     pub date: DateTime<Local>,
     pub elo: Option<u16>,
     pub premade: Option<bool>,
-    pub team_id: String,
+    /// Missing for matches from the Data API, see [`crate::DataClient::player_matches`].
+    pub team_id: Option<String>,
     pub game_mode: String,
     pub best_of: String,
     pub played: String,
@@ -69,10 +72,62 @@ pub struct Match {
 #[serde(transparent)]
 pub struct Matches(pub Vec<Match>);
 
+/// The stat accessors expect the stats to be mapped, see [`Matches::map_stats`].
+impl Match {
+    pub fn stat<T: FromStr>(&self, label: &str) -> Option<T> {
+        parse_stat(&self.stats, label)
+    }
+    pub fn map(&self) -> Option<&str> {
+        self.stats.get("Map").map(String::as_str)
+    }
+    pub fn won(&self) -> Option<bool> {
+        self.stat::<u8>("Result").map(|result| result == 1)
+    }
+    pub fn kills(&self) -> Option<u32> {
+        self.stat("Kills")
+    }
+    pub fn deaths(&self) -> Option<u32> {
+        self.stat("Deaths")
+    }
+    pub fn assists(&self) -> Option<u32> {
+        self.stat("Assists")
+    }
+    pub fn rounds(&self) -> Option<u32> {
+        self.stat("Rounds")
+    }
+    pub fn kd(&self) -> Option<f32> {
+        self.stat("K/D Ratio")
+    }
+    pub fn kr(&self) -> Option<f32> {
+        self.stat("K/R Ratio")
+    }
+    pub fn headshot_pct(&self) -> Option<f32> {
+        self.stat("Headshots %")
+    }
+    pub fn adr(&self) -> Option<f32> {
+        self.stat("ADR")
+    }
+}
+
 impl Matches {
     pub fn map_stats(&mut self, mapping: &Mapping) {
         self.0.iter_mut().for_each(|m| m.stats.map_stats(mapping))
     }
+    /// Elo gained or lost in each match, ordered like the matches.
+    ///
+    /// Expects the matches newest first, as returned by the api. The delta
+    /// is `None` if the match or the one played before it has no elo.
+    pub fn elo_deltas(&self) -> Vec<Option<i32>> {
+        let mut deltas = self
+            .0
+            .windows(2)
+            .map(|w| Some(w[0].elo? as i32 - w[1].elo? as i32))
+            .collect::<Vec<_>>();
+        if !self.0.is_empty() {
+            deltas.push(None);
+        }
+        deltas
+    }
 }
 
 impl Into<Match> for Match_ {
@@ -96,7 +151,7 @@ impl Into<Match> for Match_ {
             date,
             elo,
             premade: self.premade,
-            team_id: self.team_id,
+            team_id: Some(self.team_id),
             game_mode: self.game_mode,
             best_of: self.best_of.unwrap_or_default(),
             played: self.played.unwrap_or_default(),
@@ -131,5 +186,52 @@ impl Client {
 
         Ok(self.get_json::<Response>(&url, &query).await?.into())
     }
+
+    /// Pages through the whole match history of a player, newest first,
+    /// until the api returns an empty page.
+    pub fn match_history<'a>(
+        &'a self,
+        user_id: &'a str,
+        page_size: usize,
+    ) -> impl Stream<Item = reqwest::Result<Match>> + 'a {
+        let pages = stream::try_unfold(0, move |page| async move {
+            let matches = self.matches(user_id, page_size, page).await?;
+            if matches.0.is_empty() {
+                return Ok(None);
+            }
+            let matches = stream::iter(matches.0.into_iter().map(Ok));
+            Ok(Some((matches, page + 1)))
+        });
+        pages.try_flatten()
+    }
 }
 
+#[cfg(test)]
+impl Match {
+    /// A finished 5v5 match, `stats` are expected to be mapped.
+    pub(crate) fn fixture<'a, I, V>(
+        match_id: &str,
+        date: DateTime<Local>,
+        elo: Option<u16>,
+        stats: I,
+    ) -> Match
+    where
+        I: IntoIterator<Item = (&'a str, V)>,
+        V: ToString,
+    {
+        let stats = stats.into_iter();
+        Match {
+            match_id: match_id.to_string(),
+            date,
+            elo,
+            premade: None,
+            team_id: None,
+            game_mode: "5v5".to_string(),
+            best_of: "1".to_string(),
+            played: "1".to_string(),
+            status: "FINISHED".to_string(),
+            game: "csgo".to_string(),
+            stats: stats.map(|(k, v)| (k.to_string(), v.to_string())).collect(),
+        }
+    }
+}
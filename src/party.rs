@@ -0,0 +1,193 @@
+use crate::room::{Room, Team};
+use crate::room_stats::RoomStats;
+
+use std::cmp::Reverse;
+
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+pub struct Party {
+    /// `None` for players that queued without a party.
+    pub party_id: Option<String>,
+    pub members: Vec<String>,
+    /// Seconds the party spent in the queue.
+    pub queue_duration: Option<f32>,
+}
+
+impl Party {
+    pub fn size(&self) -> usize {
+        self.members.len()
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct FactionParties {
+    pub team_id: String,
+    /// Largest party first.
+    pub parties: Vec<Party>,
+    /// Whether the room stats flag this team as premade, `None` until
+    /// joined with [`PartyAnalysis::with_room_stats`].
+    pub premade: Option<bool>,
+}
+
+impl FactionParties {
+    fn from_team(team: &Team, room: &Room) -> FactionParties {
+        let mut parties = Vec::<Party>::new();
+        for player in team.roster.iter() {
+            let party_id = player.party_id.clone().or_else(|| {
+                let mut parties = room.parties.iter();
+                let (id, _) = parties.find(|(_, members)| members.contains(&player.id))?;
+                Some(id.clone())
+            });
+            let party = match party_id.as_ref() {
+                Some(id) => parties.iter_mut().find(|p| p.party_id.as_ref() == Some(id)),
+                None => None,
+            };
+            match party {
+                Some(party) => party.members.push(player.id.clone()),
+                None => parties.push(Party {
+                    queue_duration: party_id
+                        .as_ref()
+                        .and_then(|id| room.party_queue_durations.get(id).copied()),
+                    party_id,
+                    members: vec![player.id.clone()],
+                }),
+            }
+        }
+        parties.sort_by_key(|p| Reverse(p.size()));
+
+        FactionParties {
+            team_id: team.id.clone(),
+            parties,
+            premade: None,
+        }
+    }
+
+    pub fn sizes(&self) -> Vec<usize> {
+        self.parties.iter().map(Party::size).collect()
+    }
+    pub fn largest_party(&self) -> usize {
+        self.parties.first().map_or(0, Party::size)
+    }
+    pub fn solo_players(&self) -> usize {
+        self.parties.iter().filter(|p| p.size() == 1).count()
+    }
+    /// Players that queued together with at least one teammate.
+    pub fn premade_players(&self) -> usize {
+        self.parties
+            .iter()
+            .map(Party::size)
+            .filter(|&s| s > 1)
+            .sum()
+    }
+    pub fn longest_queue_duration(&self) -> Option<f32> {
+        let durations = self.parties.iter().filter_map(|p| p.queue_duration);
+        durations.reduce(f32::max)
+    }
+}
+
+/// How the players of a room queued, see [`Room::party_analysis`].
+#[derive(Debug, Serialize)]
+pub struct PartyAnalysis {
+    pub faction_1: FactionParties,
+    pub faction_2: FactionParties,
+}
+
+impl PartyAnalysis {
+    pub fn iter(&self) -> impl Iterator<Item = &FactionParties> {
+        [&self.faction_1, &self.faction_2].into_iter()
+    }
+
+    /// Fills in the `premade` flags of both factions.
+    pub fn with_room_stats(mut self, stats: &RoomStats) -> PartyAnalysis {
+        let teams = stats.0.iter().flat_map(|m| m.teams.iter());
+        for team in teams {
+            for faction in [&mut self.faction_1, &mut self.faction_2] {
+                if faction.team_id == team.team_id {
+                    faction.premade = Some(team.premade);
+                }
+            }
+        }
+        self
+    }
+
+    pub fn party_of(&self, player_id: &str) -> Option<&Party> {
+        let mut parties = self.iter().flat_map(|f| f.parties.iter());
+        parties.find(|p| p.members.iter().any(|id| id == player_id))
+    }
+
+    pub fn queued_together(&self, player_1: &str, player_2: &str) -> bool {
+        self.party_of(player_1)
+            .is_some_and(|p| p.members.iter().any(|id| id == player_2))
+    }
+
+    /// Difference of the largest party sizes, positive if faction 1 has the
+    /// larger party.
+    pub fn largest_party_asymmetry(&self) -> i32 {
+        self.faction_1.largest_party() as i32 - self.faction_2.largest_party() as i32
+    }
+
+    /// Difference of the players that queued with a teammate, positive if
+    /// faction 1 has more of them.
+    pub fn premade_asymmetry(&self) -> i32 {
+        self.faction_1.premade_players() as i32 - self.faction_2.premade_players() as i32
+    }
+}
+
+impl Room {
+    /// Groups the players of each faction into the parties they queued in.
+    pub fn party_analysis(&self) -> PartyAnalysis {
+        PartyAnalysis {
+            faction_1: FactionParties::from_team(&self.teams.faction_1, self),
+            faction_2: FactionParties::from_team(&self.teams.faction_2, self),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn room() -> Room {
+        let mut room = Room::fixture(
+            "room",
+            &["a", "b", "c", "d", "e"],
+            &["f", "g", "h", "i", "j"],
+        );
+        let parties = [
+            ("p1", &["a", "b", "c"][..]),
+            ("p2", &["f", "g"]),
+            ("p3", &["h", "i"]),
+        ];
+        for (party, members) in parties {
+            let members = members.iter().map(|id| id.to_string()).collect();
+            room.parties.insert(party.to_string(), members);
+        }
+        room.party_queue_durations.insert("p1".to_string(), 120.0);
+        room.party_queue_durations.insert("p2".to_string(), 45.0);
+        // The party id of the player takes precedence over the room parties.
+        room.teams.faction_1.roster[3].party_id = Some("p4".to_string());
+        room
+    }
+
+    #[test]
+    fn groups_parties() {
+        let analysis = room().party_analysis();
+
+        assert_eq!(analysis.faction_1.sizes(), [3, 1, 1]);
+        assert_eq!(analysis.faction_1.solo_players(), 2);
+        assert_eq!(analysis.faction_1.premade_players(), 3);
+        assert_eq!(analysis.faction_1.longest_queue_duration(), Some(120.0));
+        assert_eq!(analysis.faction_2.sizes(), [2, 2, 1]);
+        assert_eq!(analysis.faction_2.premade_players(), 4);
+
+        assert_eq!(analysis.largest_party_asymmetry(), 1);
+        assert_eq!(analysis.premade_asymmetry(), -1);
+        assert!(analysis.queued_together("a", "c"));
+        assert!(!analysis.queued_together("a", "d"));
+        assert!(!analysis.queued_together("f", "h"));
+        let party = analysis.party_of("d").unwrap();
+        assert_eq!(party.party_id.as_deref(), Some("p4"));
+        assert_eq!(party.queue_duration, None);
+    }
+}
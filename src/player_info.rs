@@ -59,22 +59,22 @@ struct Response {
 
 #[derive(Debug, Serialize)]
 pub struct PlayerInfo {
-    id: String,
-    country: String,
-    region: String,
-    avatar: Option<String>,
-    cover_image: Option<String>,
-    created_at: DateTime<Local>,
-    friends: Vec<String>,
-    gender: Option<String>,
-    matching_sound: Option<String>,
-    memberships: Vec<String>,
-    nickname: String,
-    phone_verified: bool,
-    game_id: String,
-    game_name: String,
-    elo: u16,
-    skill_level: u8,
+    pub id: String,
+    pub country: String,
+    pub region: String,
+    pub avatar: Option<String>,
+    pub cover_image: Option<String>,
+    pub created_at: DateTime<Local>,
+    pub friends: Vec<String>,
+    pub gender: Option<String>,
+    pub matching_sound: Option<String>,
+    pub memberships: Vec<String>,
+    pub nickname: String,
+    pub phone_verified: bool,
+    pub game_id: String,
+    pub game_name: String,
+    pub elo: u16,
+    pub skill_level: u8,
 }
 
 impl PlayerInfo {
@@ -0,0 +1,203 @@
+use crate::room::Room;
+use crate::shared::percentile;
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Local, TimeZone, Timelike};
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+pub struct Distribution {
+    pub count: usize,
+    pub mean: f32,
+    pub min: f32,
+    pub p10: f32,
+    pub p25: f32,
+    pub median: f32,
+    pub p75: f32,
+    pub p90: f32,
+    pub max: f32,
+}
+
+impl Distribution {
+    pub fn from_values(mut values: Vec<f32>) -> Option<Distribution> {
+        values.retain(|v| v.is_finite());
+        values.sort_by(f32::total_cmp);
+        let p = |p| percentile(&values, p);
+
+        Some(Distribution {
+            count: values.len(),
+            mean: values.iter().sum::<f32>() / values.len() as f32,
+            min: p(0.0)?,
+            p10: p(0.1)?,
+            p25: p(0.25)?,
+            median: p(0.5)?,
+            p75: p(0.75)?,
+            p90: p(0.9)?,
+            max: p(1.0)?,
+        })
+    }
+}
+
+/// How long a single party waited for a match.
+#[derive(Debug, Serialize)]
+pub struct QueueSample {
+    pub room_id: String,
+    pub party_id: String,
+    /// Seconds spent in the queue.
+    pub duration: f32,
+    pub party_size: usize,
+    /// Rounded average skill level of the party members.
+    pub skill_level: Option<u8>,
+    pub region: String,
+    /// `None` if the match did not start yet.
+    pub started_at: Option<DateTime<Local>>,
+}
+
+/// Queue times of every party across many rooms.
+#[derive(Debug, Default, Serialize)]
+#[serde(transparent)]
+pub struct QueueTimes(pub Vec<QueueSample>);
+
+impl QueueTimes {
+    pub fn from_rooms<'a, I>(rooms: I) -> QueueTimes
+    where
+        I: IntoIterator<Item = &'a Room>,
+    {
+        let mut queue_times = QueueTimes::default();
+        for room in rooms {
+            queue_times.add(room);
+        }
+        queue_times
+    }
+
+    pub fn add(&mut self, room: &Room) {
+        for (party_id, &duration) in room.party_queue_durations.iter() {
+            let members = room.parties.get(party_id);
+            let members = members.map(Vec::as_slice).unwrap_or_default();
+            let levels = room.players().filter(|p| members.contains(&p.id));
+            let levels = levels.map(|p| p.skill_level as f32).collect::<Vec<_>>();
+            let skill_level = (!levels.is_empty())
+                .then(|| (levels.iter().sum::<f32>() / levels.len() as f32).round() as u8);
+
+            self.0.push(QueueSample {
+                room_id: room.id.clone(),
+                party_id: party_id.clone(),
+                duration,
+                party_size: members.len().max(1),
+                skill_level,
+                region: room.region.clone(),
+                started_at: room.started_at,
+            });
+        }
+    }
+
+    pub fn distribution(&self) -> Option<Distribution> {
+        Distribution::from_values(self.0.iter().map(|s| s.duration).collect())
+    }
+
+    /// Distribution of the queue times grouped by an arbitrary key.
+    pub fn group_by<K, F>(&self, key: F) -> BTreeMap<K, Distribution>
+    where
+        K: Ord,
+        F: Fn(&QueueSample) -> K,
+    {
+        let mut groups = BTreeMap::<K, Vec<f32>>::new();
+        for sample in self.0.iter() {
+            groups.entry(key(sample)).or_default().push(sample.duration);
+        }
+        groups
+            .into_iter()
+            .filter_map(|(k, values)| Some((k, Distribution::from_values(values)?)))
+            .collect()
+    }
+
+    pub fn by_skill_level(&self) -> BTreeMap<Option<u8>, Distribution> {
+        self.group_by(|s| s.skill_level)
+    }
+    pub fn by_party_size(&self) -> BTreeMap<usize, Distribution> {
+        self.group_by(|s| s.party_size)
+    }
+    pub fn by_region(&self) -> BTreeMap<String, Distribution> {
+        self.group_by(|s| s.region.clone())
+    }
+    /// Grouped by the hour of day the match started in the given timezone,
+    /// e.g. [`chrono::Local`] or [`chrono::Utc`]. Matches that did not
+    /// start yet are left out.
+    pub fn by_hour<Tz: TimeZone>(&self, tz: &Tz) -> BTreeMap<u32, Distribution> {
+        let hours = self.group_by(|s| s.started_at.map(|at| at.with_timezone(tz).hour()));
+        hours
+            .into_iter()
+            .filter_map(|(hour, distribution)| Some((hour?, distribution)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn distribution() {
+        let values = vec![50.0, 10.0, f32::NAN, 30.0, 20.0, 40.0];
+        let d = Distribution::from_values(values).unwrap();
+        assert_eq!(d.count, 5);
+        assert_eq!((d.mean, d.min, d.median, d.max), (30.0, 10.0, 30.0, 50.0));
+        assert_eq!((d.p25, d.p75), (20.0, 40.0));
+        assert!((d.p10 - 14.0).abs() < 1e-4);
+        assert!((d.p90 - 46.0).abs() < 1e-4);
+
+        assert!(Distribution::from_values(Vec::new()).is_none());
+        assert!(Distribution::from_values(vec![f32::INFINITY]).is_none());
+    }
+
+    #[test]
+    fn samples_every_party() {
+        let mut room = Room::fixture("room", &["a", "b"], &["c", "d"]);
+        room.teams.faction_1.roster[0].skill_level = 4;
+        room.teams.faction_1.roster[1].skill_level = 7;
+        let parties = [("p1", vec!["a", "b"]), ("p2", vec!["c"])];
+        for (party, members) in parties {
+            let members = members.into_iter().map(String::from).collect();
+            room.parties.insert(party.to_string(), members);
+        }
+        let durations = [("p1", 60.0), ("p2", 30.0), ("p3", 90.0)];
+        for (party, duration) in durations {
+            room.party_queue_durations
+                .insert(party.to_string(), duration);
+        }
+
+        let queue_times = QueueTimes::from_rooms([&room]);
+        assert_eq!(queue_times.0.len(), 3);
+        let p1 = queue_times.0.iter().find(|s| s.party_id == "p1").unwrap();
+        assert_eq!((p1.party_size, p1.skill_level), (2, Some(6)));
+        // Parties without known members count as a single player.
+        let p3 = queue_times.0.iter().find(|s| s.party_id == "p3").unwrap();
+        assert_eq!((p3.party_size, p3.skill_level), (1, None));
+
+        let by_size = queue_times.by_party_size();
+        assert_eq!(by_size[&1].count, 2);
+        assert_eq!(by_size[&1].mean, 60.0);
+        assert_eq!(by_size[&2].median, 60.0);
+        assert_eq!(queue_times.by_region()["EU"].count, 3);
+    }
+
+    #[test]
+    fn buckets_by_hour_in_timezone() {
+        let mut room = Room::fixture("room", &["a"], &["b"]);
+        room.party_queue_durations.insert("p1".to_string(), 60.0);
+        let mut unstarted = Room::fixture("unstarted", &["c"], &["d"]);
+        unstarted.started_at = None;
+        unstarted
+            .party_queue_durations
+            .insert("p2".to_string(), 30.0);
+
+        let queue_times = QueueTimes::from_rooms([&room, &unstarted]);
+        // The fixture starts at 12:00 UTC.
+        let utc = queue_times.by_hour(&chrono::Utc);
+        assert_eq!(utc.keys().collect::<Vec<_>>(), [&12]);
+        assert_eq!(utc[&12].count, 1);
+        let offset = chrono::FixedOffset::east_opt(3 * 3600).unwrap();
+        assert_eq!(queue_times.by_hour(&offset)[&15].mean, 60.0);
+    }
+}
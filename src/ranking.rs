@@ -0,0 +1,217 @@
+use crate::client::Client;
+use crate::mapping::{MapStats, Mapping};
+use crate::matches::Matches;
+use crate::rate_limit::limiter;
+use crate::room::Room;
+use crate::shared::mean;
+use crate::stats::Stats;
+
+use std::cmp::Ordering;
+
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum Metric {
+    Elo,
+    Kd,
+    HeadshotPct,
+    Adr,
+    WinRate,
+}
+
+impl Metric {
+    pub const ALL: [Metric; 5] = [
+        Metric::Elo,
+        Metric::Kd,
+        Metric::HeadshotPct,
+        Metric::Adr,
+        Metric::WinRate,
+    ];
+}
+
+/// The key stats of a player, preferring the recent matches
+/// over the lifetime stats where both are available.
+#[derive(Debug, Clone, Serialize)]
+pub struct PlayerSnapshot {
+    pub player_id: String,
+    pub nickname: String,
+    pub elo: u16,
+    pub kd: Option<f32>,
+    pub headshot_pct: Option<f32>,
+    pub adr: Option<f32>,
+    /// Between `0.0` and `1.0`.
+    pub win_rate: Option<f32>,
+}
+
+impl PlayerSnapshot {
+    /// Expects the stats of `stats` and `matches` to be mapped, see [`MapStats`].
+    pub fn new(
+        player_id: &str,
+        nickname: &str,
+        elo: u16,
+        stats: &Stats,
+        matches: &Matches,
+    ) -> PlayerSnapshot {
+        let recent = matches.0.iter();
+        let wins = recent.clone().filter_map(|m| m.won());
+
+        PlayerSnapshot {
+            player_id: player_id.to_string(),
+            nickname: nickname.to_string(),
+            elo,
+            kd: mean(recent.clone().filter_map(|m| m.kd())).or_else(|| stats.kd()),
+            headshot_pct: mean(recent.clone().filter_map(|m| m.headshot_pct()))
+                .or_else(|| stats.headshot_pct()),
+            adr: mean(recent.filter_map(|m| m.adr())),
+            win_rate: mean(wins.map(|won| if won { 1.0 } else { 0.0 }))
+                .or_else(|| stats.win_rate().map(|rate| rate / 100.0)),
+        }
+    }
+
+    pub fn get(&self, metric: Metric) -> Option<f32> {
+        match metric {
+            Metric::Elo => Some(self.elo as f32),
+            Metric::Kd => self.kd,
+            Metric::HeadshotPct => self.headshot_pct,
+            Metric::Adr => self.adr,
+            Metric::WinRate => self.win_rate,
+        }
+    }
+}
+
+/// Percentiles between `0.0` and `100.0`, `None` if the player
+/// or every other player lacks the stat.
+#[derive(Debug, Serialize)]
+pub struct Ranks {
+    pub elo: Option<f32>,
+    pub kd: Option<f32>,
+    pub headshot_pct: Option<f32>,
+    pub adr: Option<f32>,
+    pub win_rate: Option<f32>,
+}
+
+/// A group of players to rank against, e.g. the ten players of a room or
+/// every tracked member of a hub.
+#[derive(Debug, Default, Serialize)]
+#[serde(transparent)]
+pub struct Cohort(pub Vec<PlayerSnapshot>);
+
+impl Cohort {
+    /// Share of players with a lower value, counting equal values half.
+    pub fn percentile(&self, metric: Metric, value: f32) -> Option<f32> {
+        let values = self.0.iter().filter_map(|p| p.get(metric));
+        let (below, equal, total) = values.fold((0, 0, 0), |(below, equal, total), v| {
+            match v.total_cmp(&value) {
+                Ordering::Less => (below + 1, equal, total + 1),
+                Ordering::Equal => (below, equal + 1, total + 1),
+                Ordering::Greater => (below, equal, total + 1),
+            }
+        });
+        (total > 0).then(|| (below as f32 + equal as f32 / 2.0) / total as f32 * 100.0)
+    }
+
+    pub fn rank(&self, player: &PlayerSnapshot) -> Ranks {
+        let rank = |metric| self.percentile(metric, player.get(metric)?);
+        Ranks {
+            elo: rank(Metric::Elo),
+            kd: rank(Metric::Kd),
+            headshot_pct: rank(Metric::HeadshotPct),
+            adr: rank(Metric::Adr),
+            win_rate: rank(Metric::WinRate),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct PlayerRanking {
+    pub player_id: String,
+    pub nickname: String,
+    pub lobby: Ranks,
+    pub cohort: Option<Ranks>,
+}
+
+/// Ranks every player of a lobby among the lobby and optionally
+/// among a tracked cohort.
+pub fn rank_lobby(lobby: &Cohort, cohort: Option<&Cohort>) -> Vec<PlayerRanking> {
+    lobby
+        .0
+        .iter()
+        .map(|player| PlayerRanking {
+            player_id: player.player_id.clone(),
+            nickname: player.nickname.clone(),
+            lobby: lobby.rank(player),
+            cohort: cohort.map(|cohort| cohort.rank(player)),
+        })
+        .collect()
+}
+
+impl Client {
+    /// Fetches the lifetime stats and the last `recent` matches of every
+    /// player in the room, e.g. before the match started, see [`Client::room`].
+    ///
+    /// Makes two requests per player with at most `per_sec` requests per
+    /// second, clamped between 1 and 1000.
+    pub async fn lobby(
+        &self,
+        room: &Room,
+        mapping: &Mapping,
+        recent: usize,
+        per_sec: u64,
+    ) -> reqwest::Result<Cohort> {
+        let mut limiter = limiter(per_sec);
+        let mut snapshots = Vec::new();
+        for player in room.players() {
+            limiter.tick().await;
+            let mut stats = self.stats(&player.id).await?;
+            limiter.tick().await;
+            let mut matches = self.matches(&player.id, recent, 0).await?;
+            stats.map_stats(mapping);
+            matches.map_stats(mapping);
+
+            let snapshot =
+                PlayerSnapshot::new(&player.id, &player.nickname, player.elo, &stats, &matches);
+            snapshots.push(snapshot);
+        }
+        Ok(Cohort(snapshots))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(player_id: &str, elo: u16, kd: Option<f32>) -> PlayerSnapshot {
+        PlayerSnapshot {
+            player_id: player_id.to_string(),
+            nickname: player_id.to_string(),
+            elo,
+            kd,
+            headshot_pct: None,
+            adr: None,
+            win_rate: None,
+        }
+    }
+
+    #[test]
+    fn counts_ties_half() {
+        let cohort = Cohort(vec![
+            snapshot("a", 1000, Some(0.8)),
+            snapshot("b", 1200, Some(1.0)),
+            snapshot("c", 1200, Some(1.0)),
+            snapshot("d", 1500, None),
+        ]);
+
+        // One player below and two equal out of four.
+        assert_eq!(cohort.percentile(Metric::Elo, 1200.0), Some(50.0));
+        assert_eq!(cohort.percentile(Metric::Elo, 1500.0), Some(87.5));
+        assert_eq!(cohort.percentile(Metric::Elo, 900.0), Some(0.0));
+        // Players without the stat are left out.
+        let kd = cohort.percentile(Metric::Kd, 1.0).unwrap();
+        assert!((kd - 200.0 / 3.0).abs() < 1e-4);
+        assert_eq!(cohort.percentile(Metric::Adr, 80.0), None);
+
+        let ranks = cohort.rank(&cohort.0[3]);
+        assert_eq!(ranks.elo, Some(87.5));
+        assert_eq!(ranks.kd, None);
+    }
+}
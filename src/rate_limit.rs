@@ -12,8 +12,9 @@ where
     stream
 }
 
-fn limiter(per_sec: u64) -> Interval {
-    let delay_ms = ((1.0 / per_sec as f64) * 1_000.0) as u64;
+/// Ticks at most `per_sec` times per second, clamped between `1` and `1000`.
+pub(crate) fn limiter(per_sec: u64) -> Interval {
+    let delay_ms = 1_000 / per_sec.clamp(1, 1_000);
     let mut limiter = interval(Duration::from_millis(delay_ms));
     limiter.set_missed_tick_behavior(MissedTickBehavior::Delay);
     limiter
@@ -58,3 +59,15 @@ where
         self.iter.size_hint()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn clamps_the_rate() {
+        assert_eq!(limiter(0).period(), Duration::from_secs(1));
+        assert_eq!(limiter(4).period(), Duration::from_millis(250));
+        assert_eq!(limiter(5_000).period(), Duration::from_millis(1));
+    }
+}
@@ -0,0 +1,226 @@
+use crate::matches::Match;
+use crate::room_stats::MatchStats;
+use crate::shared::parse_stat;
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+// Averages the HLTV 1.0 rating normalizes against.
+const AVERAGE_KPR: f32 = 0.679;
+const AVERAGE_SPR: f32 = 0.317;
+const AVERAGE_MULTI_KILL: f32 = 1.277;
+
+/// HLTV 1.0-like rating of a single match.
+///
+/// FACEIT only reports triple, quadro and penta kills, so the rounds with
+/// one and two kills are estimated from the remaining kills. Impact uses
+/// the HLTV 2.0 approximation from kills and assists per round. KAST
+/// counts every survived round and estimates how many of the other rounds
+/// had a kill or assist, trades are not taken into account.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct Rating {
+    pub rounds: u32,
+    pub rating: f32,
+    pub impact: f32,
+    pub kast: f32,
+}
+
+impl Rating {
+    /// Computes the rating from mapped player stats, see [`crate::MapStats`].
+    pub fn from_stats(stats: &HashMap<String, String>, rounds: u32) -> Option<Rating> {
+        if rounds == 0 {
+            return None;
+        }
+        let kills = parse_stat::<u32>(stats, "Kills")?;
+        let deaths = parse_stat::<u32>(stats, "Deaths")?;
+        let assists = parse_stat::<u32>(stats, "Assists").unwrap_or_default();
+        let triple = parse_stat::<u32>(stats, "Triple Kills").unwrap_or_default();
+        let quadro = parse_stat::<u32>(stats, "Quadro Kills").unwrap_or_default();
+        let penta = parse_stat::<u32>(stats, "Penta Kills").unwrap_or_default();
+
+        let r = rounds as f32;
+        let kpr = kills as f32 / r;
+        let apr = assists as f32 / r;
+        let spr = rounds.saturating_sub(deaths) as f32 / r;
+
+        // Assume the kills outside of 3k+ rounds are poisson distributed
+        // and only 1k and 2k rounds are left, then 2k = 1k * lambda / 2.
+        let remaining = kills.saturating_sub(3 * triple + 4 * quadro + 5 * penta) as f32;
+        let other_rounds = rounds.saturating_sub(triple + quadro + penta).max(1) as f32;
+        let lambda = remaining / other_rounds;
+        let one = remaining / (1.0 + lambda);
+        let two = one * lambda / 2.0;
+
+        let multi_kill =
+            one + 4.0 * two + 9.0 * triple as f32 + 16.0 * quadro as f32 + 25.0 * penta as f32;
+        let multi_kill = multi_kill / r;
+
+        let rating =
+            (kpr / AVERAGE_KPR + 0.7 * spr / AVERAGE_SPR + multi_kill / AVERAGE_MULTI_KILL) / 2.7;
+        let impact = 2.13 * kpr + 0.42 * apr - 0.41;
+        let death_rounds = 1.0 - spr;
+        let kast = spr + death_rounds * (1.0 - (-(kpr + apr)).exp());
+
+        Some(Rating {
+            rounds,
+            rating,
+            impact,
+            kast: kast.clamp(0.0, 1.0),
+        })
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct PlayerRating {
+    pub player_id: String,
+    pub nickname: String,
+    pub team_id: String,
+    pub rating: Rating,
+}
+
+impl MatchStats {
+    /// Rates every player, expects the stats to be mapped, see [`crate::MapStats`].
+    pub fn ratings(&self) -> Vec<PlayerRating> {
+        let rounds = match parse_stat::<u32>(&self.stats, "Rounds") {
+            Some(rounds) => rounds,
+            None => return Vec::new(),
+        };
+        self.teams
+            .iter()
+            .flat_map(|team| team.players.iter().map(move |p| (team, p)))
+            .filter_map(|(team, player)| {
+                Some(PlayerRating {
+                    player_id: player.player_id.clone(),
+                    nickname: player.nickname.clone(),
+                    team_id: team.team_id.clone(),
+                    rating: Rating::from_stats(&player.stats, rounds)?,
+                })
+            })
+            .collect()
+    }
+}
+
+impl Match {
+    /// Rates the player, expects the stats to be mapped, see [`crate::Matches::map_stats`].
+    pub fn rating(&self) -> Option<Rating> {
+        Rating::from_stats(&self.stats, self.rounds()?)
+    }
+}
+
+/// Ratings of many matches, weighted by the rounds played.
+#[derive(Debug, Serialize)]
+pub struct RatingSummary {
+    pub matches: usize,
+    pub rounds: u32,
+    pub rating: f32,
+    pub impact: f32,
+    pub kast: f32,
+}
+
+impl RatingSummary {
+    pub fn aggregate<'a, I>(ratings: I) -> Option<RatingSummary>
+    where
+        I: IntoIterator<Item = &'a Rating>,
+    {
+        let (mut matches, mut rounds) = (0, 0);
+        let (mut rating, mut impact, mut kast) = (0.0, 0.0, 0.0);
+        for r in ratings {
+            let weight = r.rounds as f32;
+            matches += 1;
+            rounds += r.rounds;
+            rating += r.rating * weight;
+            impact += r.impact * weight;
+            kast += r.kast * weight;
+        }
+        if rounds == 0 {
+            return None;
+        }
+
+        let total = rounds as f32;
+        Some(RatingSummary {
+            matches,
+            rounds,
+            rating: rating / total,
+            impact: impact / total,
+            kast: kast / total,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(pairs: &[(&str, u32)]) -> HashMap<String, String> {
+        let pairs = pairs.iter().map(|(k, v)| (k.to_string(), v.to_string()));
+        pairs.collect()
+    }
+
+    fn rating(rounds: u32, value: f32) -> Rating {
+        Rating {
+            rounds,
+            rating: value,
+            impact: value,
+            kast: value / 2.0,
+        }
+    }
+
+    #[test]
+    fn average_stat_line_rates_one() {
+        // KPR 0.679, SPR 0.317 and a multi-kill rating of about 1.277.
+        let stats = stats(&[
+            ("Kills", 679),
+            ("Deaths", 683),
+            ("Triple Kills", 39),
+            ("Quadro Kills", 9),
+            ("Penta Kills", 4),
+        ]);
+        let rating = Rating::from_stats(&stats, 1000).unwrap();
+        assert!((rating.rating - 1.0).abs() < 0.01, "{}", rating.rating);
+        assert!((rating.impact - (2.13 * 0.679 - 0.41)).abs() < 1e-4);
+    }
+
+    #[test]
+    fn estimates_multi_kills_and_kast() {
+        // Every kill in a single triple kill round, no survived rounds.
+        let line = stats(&[("Kills", 3), ("Deaths", 10), ("Triple Kills", 1)]);
+        let rating = Rating::from_stats(&line, 10).unwrap();
+        let expected = (0.3 / AVERAGE_KPR + 0.9 / AVERAGE_MULTI_KILL) / 2.7;
+        assert!((rating.rating - expected).abs() < 1e-4);
+
+        // 5 kills over 10 rounds, 1k = 5 / 1.5 and 2k = 1k * 0.5 / 2.
+        let line = stats(&[("Kills", 5), ("Deaths", 10)]);
+        let rating = Rating::from_stats(&line, 10).unwrap();
+        let one = 5.0 / 1.5;
+        let multi_kill = (one + 4.0 * one * 0.25) / 10.0;
+        let expected = (0.5 / AVERAGE_KPR + multi_kill / AVERAGE_MULTI_KILL) / 2.7;
+        assert!((rating.rating - expected).abs() < 1e-4);
+        assert!((rating.kast - (1.0 - (-0.5f32).exp())).abs() < 1e-4);
+
+        // Surviving every round counts every round for KAST.
+        let line = stats(&[("Kills", 0), ("Deaths", 0)]);
+        assert_eq!(Rating::from_stats(&line, 10).unwrap().kast, 1.0);
+    }
+
+    #[test]
+    fn requires_rounds_and_kills() {
+        let complete = stats(&[("Kills", 10), ("Deaths", 10)]);
+        assert!(Rating::from_stats(&complete, 0).is_none());
+        assert!(Rating::from_stats(&stats(&[("Deaths", 10)]), 20).is_none());
+    }
+
+    #[test]
+    fn weights_summary_by_rounds() {
+        let ratings = [rating(10, 1.0), rating(30, 2.0)];
+        let summary = RatingSummary::aggregate(&ratings).unwrap();
+        assert_eq!(summary.matches, 2);
+        assert_eq!(summary.rounds, 40);
+        assert!((summary.rating - 1.75).abs() < 1e-6);
+        assert!((summary.impact - 1.75).abs() < 1e-6);
+        assert!((summary.kast - 0.875).abs() < 1e-6);
+
+        assert!(RatingSummary::aggregate(&[]).is_none());
+        assert!(RatingSummary::aggregate(&[rating(0, 1.0)]).is_none());
+    }
+}
@@ -0,0 +1,115 @@
+use crate::room::Room;
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+#[derive(Debug, Default, Serialize)]
+pub struct PlayerReliability {
+    pub player_id: String,
+    pub matches: usize,
+    pub left: usize,
+    pub afk: usize,
+    /// Matches the player left or went afk in, counted once if both.
+    pub incidents: usize,
+}
+
+impl PlayerReliability {
+    /// Share of matches without an incident, `1.0` if the player
+    /// never left or went afk.
+    pub fn score(&self) -> f32 {
+        match self.matches {
+            0 => 1.0,
+            matches => 1.0 - self.incidents as f32 / matches as f32,
+        }
+    }
+}
+
+/// Leavers and afk players across many rooms, keyed by player id.
+#[derive(Debug, Default, Serialize)]
+#[serde(transparent)]
+pub struct Reliability(pub HashMap<String, PlayerReliability>);
+
+impl Reliability {
+    pub fn from_rooms<'a, I>(rooms: I) -> Reliability
+    where
+        I: IntoIterator<Item = &'a Room>,
+    {
+        let mut reliability = Reliability::default();
+        for room in rooms {
+            reliability.add(room);
+        }
+        reliability
+    }
+
+    pub fn add(&mut self, room: &Room) {
+        let summary = &room.summary_results;
+        for player in room.players() {
+            let left = summary.leavers.contains(&player.id);
+            let afk = summary.afk.contains(&player.id);
+
+            let entry = self.0.entry(player.id.clone());
+            let entry = entry.or_insert_with(|| PlayerReliability {
+                player_id: player.id.clone(),
+                ..Default::default()
+            });
+            entry.matches += 1;
+            entry.left += usize::from(left);
+            entry.afk += usize::from(afk);
+            entry.incidents += usize::from(left || afk);
+        }
+    }
+
+    pub fn get(&self, player_id: &str) -> Option<&PlayerReliability> {
+        self.0.get(player_id)
+    }
+
+    /// Every player with at least one incident, least reliable first.
+    pub fn offenders(&self) -> Vec<&PlayerReliability> {
+        let mut offenders = self
+            .0
+            .values()
+            .filter(|p| p.incidents > 0)
+            .collect::<Vec<_>>();
+        offenders.sort_by(|a, b| {
+            a.score()
+                .total_cmp(&b.score())
+                .then_with(|| b.incidents.cmp(&a.incidents))
+                .then_with(|| a.player_id.cmp(&b.player_id))
+        });
+        offenders
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn room(id: &str, leavers: &[&str], afk: &[&str]) -> Room {
+        let mut room = Room::fixture(id, &["a", "b"], &["c", "d"]);
+        room.summary_results.leavers = leavers.iter().map(|id| id.to_string()).collect();
+        room.summary_results.afk = afk.iter().map(|id| id.to_string()).collect();
+        room
+    }
+
+    #[test]
+    fn counts_incidents_once() {
+        let rooms = [
+            room("r1", &["a"], &["a"]),
+            room("r2", &["b"], &[]),
+            room("r3", &[], &["a", "c"]),
+            room("r4", &[], &[]),
+        ];
+        let reliability = Reliability::from_rooms(&rooms);
+
+        let a = reliability.get("a").unwrap();
+        assert_eq!((a.matches, a.left, a.afk, a.incidents), (4, 1, 2, 2));
+        assert_eq!(a.score(), 0.5);
+        assert_eq!(reliability.get("d").unwrap().score(), 1.0);
+        assert!(reliability.get("e").is_none());
+
+        let offenders = reliability.offenders().into_iter();
+        let offenders = offenders.map(|p| p.player_id.as_str()).collect::<Vec<_>>();
+        assert_eq!(offenders, ["a", "b", "c"]);
+    }
+}
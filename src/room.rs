@@ -1,5 +1,5 @@
 use crate::client::Client;
-use crate::shared::parse_rfc3339;
+use crate::shared::parse_time;
 
 use std::time::Duration;
 use std::{collections::HashMap, fmt::Debug};
@@ -11,8 +11,9 @@ use serde::{Deserialize, Serialize};
 struct EntityCustom {
     #[serde(rename = "effectiveRanking")]
     effective_ranking: Option<f32>,
+    #[serde(default)]
     parties: HashMap<String, Vec<String>>,
-    #[serde(rename = "partyQueueDurations")]
+    #[serde(rename = "partyQueueDurations", default)]
     party_queue_durations: HashMap<String, f32>,
 }
 
@@ -75,11 +76,11 @@ struct Teams_ {
     faction_2: Team_,
 }
 
-#[derive(Deserialize, Debug)]
-struct Server {
-    country: String,
-    ip: String,
-    port: String,
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Server {
+    pub country: String,
+    pub ip: String,
+    pub port: String,
 }
 
 #[derive(Deserialize, Debug)]
@@ -93,20 +94,30 @@ struct ClientCustom {
     server: Server,
 }
 
-#[derive(Deserialize, Debug)]
-struct SummaryResults {
-    leavers: Vec<String>,
-    afk: Vec<String>,
+#[derive(Deserialize, Serialize, Debug, Default)]
+pub struct SummaryResults {
+    pub leavers: Vec<String>,
+    pub afk: Vec<String>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, Default)]
 struct VotingMap {
+    #[serde(default)]
     pick: Vec<String>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, Default)]
 struct Voting {
+    #[serde(default)]
     map: VotingMap,
+    location: Option<VotingMap>,
+}
+
+/// Maps won by each faction, keyed by `faction1` and `faction2`.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct MatchResult {
+    pub winner: String,
+    pub score: HashMap<String, u8>,
 }
 
 #[derive(Deserialize, Debug)]
@@ -117,11 +128,11 @@ struct Payload {
     #[serde(rename = "organizerId")]
     organizer_id: String,
     #[serde(rename = "startedAt")]
-    started_at: String,
+    started_at: Option<String>,
     #[serde(rename = "configuredAt")]
-    configured_at: String,
+    configured_at: Option<String>,
     #[serde(rename = "finishedAt")]
-    finished_at: String,
+    finished_at: Option<String>,
     #[serde(rename = "timeToConnect")]
     time_to_connect: usize,
     version: usize,
@@ -138,9 +149,12 @@ struct Payload {
     teams: Teams_,
     #[serde(rename = "clientCustom")]
     client_custom: Option<ClientCustom>,
-    #[serde(rename = "summaryResults")]
+    #[serde(rename = "summaryResults", default)]
     summary_results: SummaryResults,
+    #[serde(default)]
     voting: Voting,
+    #[serde(default)]
+    results: Vec<MatchResult>,
     #[serde(rename = "entityCustom")]
     entity_custom: EntityCustom,
 }
@@ -152,57 +166,68 @@ struct Response {
     payload: Payload,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, Debug)]
 pub struct Stats {
-    win_probability: f32,
-    rating: u16,
+    pub win_probability: f32,
+    pub rating: u16,
 }
 
 #[derive(Serialize)]
 pub struct Player {
-    id: String,
-    nickname: String,
-    avatar: Option<String>,
-    game_id: String,
-    elo: u16,
-    skill_level: u8,
-    memberships: Vec<String>,
+    pub id: String,
+    pub nickname: String,
+    pub avatar: Option<String>,
+    pub game_id: String,
+    pub elo: u16,
+    pub skill_level: u8,
+    pub memberships: Vec<String>,
+    pub party_id: Option<String>,
 }
 
 #[derive(Serialize)]
 pub struct Team {
-    id: String,
-    name: String,
-    avatar: Option<String>,
-    leader: String,
-    roster: Vec<Player>,
+    pub id: String,
+    pub name: String,
+    pub avatar: Option<String>,
+    pub leader: String,
+    pub roster: Vec<Player>,
+    pub stats: Option<Stats>,
 }
 
 #[derive(Serialize)]
 pub struct Teams {
-    faction_1: Team,
-    faction_2: Team,
+    pub faction_1: Team,
+    pub faction_2: Team,
 }
 
 #[derive(Serialize)]
 pub struct Room {
-    id: String,
-    maps: Vec<String>,
-    started_at: DateTime<Local>,
-    configured_at: DateTime<Local>,
-    finished_at: DateTime<Local>,
-    match_duration: f32,
-    party_queue_durations: HashMap<String, f32>,
-    parties: HashMap<String, Vec<String>>,
-    teams: Teams,
+    pub id: String,
+    pub region: String,
+    pub organizer_id: String,
+    pub maps: Vec<String>,
+    pub location: Option<String>,
+    pub server: Option<Server>,
+    /// `None` until the match started.
+    pub started_at: Option<DateTime<Local>>,
+    /// `None` until the server is configured.
+    pub configured_at: Option<DateTime<Local>>,
+    /// `None` until the match finished.
+    pub finished_at: Option<DateTime<Local>>,
+    /// Seconds from start to finish, `None` until the match finished.
+    pub match_duration: Option<f32>,
+    pub party_queue_durations: HashMap<String, f32>,
+    pub parties: HashMap<String, Vec<String>>,
+    pub teams: Teams,
+    pub summary_results: SummaryResults,
+    pub results: Vec<MatchResult>,
 }
 
 impl Room {
-    pub fn duration(&self) -> Duration {
-        self.finished_at
-            .signed_duration_since(self.started_at)
-            .to_std()
-            .unwrap()
+    /// `None` until the match finished.
+    pub fn duration(&self) -> Option<Duration> {
+        let duration = self.finished_at?.signed_duration_since(self.started_at?);
+        duration.to_std().ok()
     }
     pub fn longest_queue_duration(&self) -> f32 {
         self.party_queue_durations
@@ -211,11 +236,42 @@ impl Room {
             .reduce(f32::max)
             .unwrap_or_default()
     }
+    pub fn players(&self) -> impl Iterator<Item = &Player> {
+        self.teams.iter().flat_map(|team| team.roster.iter())
+    }
+    /// The faction that won the room, `faction1` or `faction2`.
+    pub fn winner(&self) -> Option<&str> {
+        self.results.last().map(|result| result.winner.as_str())
+    }
+    /// The faction the player played for, `faction1` or `faction2`.
+    pub fn faction_of(&self, player_id: &str) -> Option<&'static str> {
+        let in_team = |team: &Team| team.roster.iter().any(|p| p.id == player_id);
+        match (in_team(&self.teams.faction_1), in_team(&self.teams.faction_2)) {
+            (true, _) => Some("faction1"),
+            (_, true) => Some("faction2"),
+            _ => None,
+        }
+    }
+}
+
+impl Team {
+    pub fn average_elo(&self) -> f32 {
+        match self.roster.len() {
+            0 => 0.0,
+            len => self.roster.iter().map(|p| p.elo as f32).sum::<f32>() / len as f32,
+        }
+    }
+}
+
+impl Teams {
+    pub fn iter(&self) -> impl Iterator<Item = &Team> {
+        [&self.faction_1, &self.faction_2].into_iter()
+    }
 }
 
 impl Debug for Room {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let dur = self.duration().as_secs_f32();
+        let dur = self.duration().map(|dur| dur.as_secs_f32());
         let queue_dur = self.longest_queue_duration();
         f.debug_struct("Room")
             .field("id", &self.id)
@@ -236,6 +292,7 @@ impl Into<Player> for Player_ {
             elo: self.elo,
             skill_level: self.game_skill_level,
             memberships: self.memberships,
+            party_id: self.party_id,
         }
     }
 }
@@ -248,6 +305,10 @@ impl Into<Team> for Team_ {
             avatar: self.avatar,
             leader: self.leader,
             roster: self.roster.into_iter().map(|p| p.into()).collect(),
+            stats: self.stats.map(|stats| Stats {
+                win_probability: stats.win_probability,
+                rating: stats.rating,
+            }),
         }
     }
 }
@@ -264,27 +325,43 @@ impl Into<Teams> for Teams_ {
 impl Into<Room> for Response {
     fn into(self) -> Room {
         let pl = self.payload;
-        let started_at = parse_rfc3339(&pl.started_at);
-        let finished_at = parse_rfc3339(&pl.finished_at);
-        let configured_at = parse_rfc3339(&pl.configured_at);
-        let match_duration = finished_at.signed_duration_since(started_at);
-        let match_duration = match_duration.to_std().unwrap();
+        let started_at = parse_time(pl.started_at.as_deref());
+        let finished_at = parse_time(pl.finished_at.as_deref());
+        let configured_at = parse_time(pl.configured_at.as_deref());
+        let match_duration = match (started_at, finished_at) {
+            (Some(started_at), Some(finished_at)) => {
+                let duration = finished_at.signed_duration_since(started_at).to_std();
+                duration.ok().map(|duration| duration.as_secs_f32())
+            }
+            _ => None,
+        };
+
+        let location = pl.voting.location.and_then(|l| l.pick.into_iter().next());
 
         Room {
             id: pl.id,
+            region: pl.region,
+            organizer_id: pl.organizer_id,
             maps: pl.voting.map.pick,
+            location,
+            server: pl.client_custom.map(|c| c.server),
             started_at,
             configured_at,
             finished_at,
-            match_duration: match_duration.as_secs_f32(),
+            match_duration,
             party_queue_durations: pl.entity_custom.party_queue_durations,
             parties: pl.entity_custom.parties,
             teams: pl.teams.into(),
+            summary_results: pl.summary_results,
+            results: pl.results,
         }
     }
 }
 
 impl Client {
+    /// Works for rooms that did not start or finish yet, their
+    /// timestamps are `None` until then.
+    ///
     /// - `https://api.faceit.com/match/v2/match/{MATCH_ID}`
     pub async fn room(&self, room_id: &str) -> reqwest::Result<Room> {
         const PREFIX: &str = "https://api.faceit.com/match/v2/match";
@@ -295,3 +372,124 @@ impl Client {
     }
 }
 
+#[cfg(test)]
+impl Room {
+    /// A finished room without parties, the first player of each faction leads it.
+    pub(crate) fn fixture(id: &str, faction_1: &[&str], faction_2: &[&str]) -> Room {
+        use crate::shared::unix_time_sec;
+
+        let team = |id: &str, roster: &[&str]| Team {
+            id: id.to_string(),
+            name: format!("team_{}", id),
+            avatar: None,
+            leader: roster.first().map(|p| p.to_string()).unwrap_or_default(),
+            roster: roster
+                .iter()
+                .map(|id| Player {
+                    id: id.to_string(),
+                    nickname: id.to_string(),
+                    avatar: None,
+                    game_id: id.to_string(),
+                    elo: 1000,
+                    skill_level: 5,
+                    memberships: Vec::new(),
+                    party_id: None,
+                })
+                .collect(),
+            stats: None,
+        };
+        let started_at = unix_time_sec(1_662_033_600);
+
+        Room {
+            id: id.to_string(),
+            region: "EU".to_string(),
+            organizer_id: "faceit".to_string(),
+            maps: vec!["de_dust2".to_string()],
+            location: None,
+            server: None,
+            started_at: Some(started_at),
+            configured_at: Some(started_at),
+            finished_at: Some(started_at + chrono::Duration::minutes(30)),
+            match_duration: Some(1800.0),
+            party_queue_durations: HashMap::new(),
+            parties: HashMap::new(),
+            teams: Teams {
+                faction_1: team("1", faction_1),
+                faction_2: team("2", faction_2),
+            },
+            summary_results: SummaryResults::default(),
+            results: Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn configuring_room() {
+        let json = r#"{
+            "time": 1662033600000,
+            "version": "v2",
+            "payload": {
+                "id": "1-room",
+                "game": "csgo",
+                "region": "EU",
+                "organizerId": "faceit",
+                "configuredAt": "",
+                "timeToConnect": 300,
+                "version": 3,
+                "createdAt": "2022-09-01T12:00:00Z",
+                "lastModified": "2022-09-01T12:01:00Z",
+                "anticheatRequired": true,
+                "anticheatMode": "mandatory",
+                "state": "CONFIGURING",
+                "status": "CONFIGURING",
+                "teams": {
+                    "faction1": {
+                        "id": "1",
+                        "name": "team_a",
+                        "leader": "a",
+                        "roster": [{
+                            "id": "a",
+                            "nickname": "a",
+                            "gameId": "1",
+                            "memberships": [],
+                            "elo": 2100,
+                            "gameSkillLevel": 10,
+                            "acReq": true,
+                            "partyId": "p1"
+                        }],
+                        "substituted": false
+                    },
+                    "faction2": {
+                        "id": "2",
+                        "name": "team_b",
+                        "leader": "b",
+                        "roster": [],
+                        "substituted": false
+                    }
+                },
+                "voting": { "map": { "pick": ["de_dust2"] } },
+                "entityCustom": {
+                    "parties": { "p1": ["a"] },
+                    "partyQueueDurations": { "p1": 42.5 }
+                }
+            }
+        }"#;
+        let room: Room = serde_json::from_str::<Response>(json).unwrap().into();
+
+        assert_eq!(room.id, "1-room");
+        assert_eq!(room.maps, ["de_dust2"]);
+        assert!(room.server.is_none());
+        assert!(room.started_at.is_none());
+        assert!(room.configured_at.is_none());
+        assert!(room.finished_at.is_none());
+        assert!(room.match_duration.is_none());
+        assert!(room.duration().is_none());
+        assert!(room.winner().is_none());
+        assert_eq!(room.longest_queue_duration(), 42.5);
+        assert_eq!(room.faction_of("a"), Some("faction1"));
+    }
+}
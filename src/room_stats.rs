@@ -1,19 +1,20 @@
 use crate::client::Client;
-use crate::shared::unix_time_ms;
+use crate::shared::{mean, parse_stat, unix_time_ms};
 use crate::MapStats;
 
+use std::str::FromStr;
 use std::{collections::HashMap, fmt::Debug};
 
 use chrono::{DateTime, Local};
 use serde::{Deserialize, Serialize};
 
 #[derive(Deserialize, Serialize, Debug)]
-struct Player {
-    nickname: String,
+pub struct PlayerStats {
+    pub nickname: String,
     #[serde(rename = "playerId")]
-    player_id: String,
+    pub player_id: String,
     #[serde(flatten)]
-    stats: HashMap<String, String>,
+    pub stats: HashMap<String, String>,
 }
 
 #[derive(Deserialize, Debug)]
@@ -21,7 +22,7 @@ struct Team_ {
     premade: bool,
     #[serde(rename = "teamId")]
     team_id: String,
-    players: Vec<Player>,
+    players: Vec<PlayerStats>,
     #[serde(flatten)]
     stats: HashMap<String, String>,
 }
@@ -52,16 +53,18 @@ struct MatchStats_ {
 struct Response(Vec<MatchStats_>);
 
 #[derive(Serialize, Debug)]
-struct Team {
-    team_id: String,
-    players: Vec<Player>,
-    stats: HashMap<String, String>,
+pub struct TeamStats {
+    pub team_id: String,
+    pub premade: bool,
+    pub players: Vec<PlayerStats>,
+    pub stats: HashMap<String, String>,
 }
 
-impl Into<Team> for Team_ {
-    fn into(self) -> Team {
-        Team {
+impl Into<TeamStats> for Team_ {
+    fn into(self) -> TeamStats {
+        TeamStats {
             team_id: self.team_id,
+            premade: self.premade,
             players: self.players,
             stats: self.stats,
         }
@@ -70,18 +73,96 @@ impl Into<Team> for Team_ {
 
 #[derive(Serialize, Debug)]
 pub struct MatchStats {
-    date: DateTime<Local>,
-    game: String,
-    game_mode: String,
-    match_id: String,
-    played: String,
-    teams: Vec<Team>,
-    stats: HashMap<String, String>,
+    pub date: DateTime<Local>,
+    pub best_of: u8,
+    /// Which map of the series this is, starting at `1`.
+    pub round: u8,
+    pub game: String,
+    pub game_mode: String,
+    pub match_id: String,
+    pub played: String,
+    pub teams: Vec<TeamStats>,
+    pub stats: HashMap<String, String>,
 }
 
 #[derive(Serialize, Debug)]
 pub struct RoomStats(pub Vec<MatchStats>);
 
+/// Result and aggregated player stats of a team on a single map.
+#[derive(Serialize, Debug)]
+pub struct TeamResult {
+    pub team_id: String,
+    pub name: Option<String>,
+    pub premade: bool,
+    pub won: Option<bool>,
+    pub final_score: Option<u8>,
+    pub first_half_score: Option<u8>,
+    pub second_half_score: Option<u8>,
+    pub overtime_score: Option<u8>,
+    pub kills: u32,
+    pub deaths: u32,
+    pub assists: u32,
+    pub average_kd: Option<f32>,
+    pub triple_kills: u32,
+    pub quadro_kills: u32,
+    pub penta_kills: u32,
+}
+
+/// The stat accessors expect the stats to be mapped, see [`MapStats`].
+impl PlayerStats {
+    pub fn stat<T: FromStr>(&self, label: &str) -> Option<T> {
+        parse_stat(&self.stats, label)
+    }
+}
+
+/// The stat accessors expect the stats to be mapped, see [`MapStats`].
+impl TeamStats {
+    pub fn stat<T: FromStr>(&self, label: &str) -> Option<T> {
+        parse_stat(&self.stats, label)
+    }
+    /// Sum of a player stat over every player of the team.
+    pub fn total(&self, label: &str) -> u32 {
+        let players = self.players.iter();
+        players.filter_map(|p| p.stat::<u32>(label)).sum()
+    }
+    /// Average of a player stat over the players that have it.
+    pub fn average(&self, label: &str) -> Option<f32> {
+        mean(self.players.iter().filter_map(|p| p.stat(label)))
+    }
+    pub fn result(&self) -> TeamResult {
+        TeamResult {
+            team_id: self.team_id.clone(),
+            name: self.stats.get("Team").cloned(),
+            premade: self.premade,
+            won: self.stat::<u8>("Team Win").map(|won| won == 1),
+            final_score: self.stat("Final Score"),
+            first_half_score: self.stat("First Half Score"),
+            second_half_score: self.stat("Second Half Score"),
+            overtime_score: self.stat("Overtime score"),
+            kills: self.total("Kills"),
+            deaths: self.total("Deaths"),
+            assists: self.total("Assists"),
+            average_kd: self.average("K/D Ratio"),
+            triple_kills: self.total("Triple Kills"),
+            quadro_kills: self.total("Quadro Kills"),
+            penta_kills: self.total("Penta Kills"),
+        }
+    }
+}
+
+impl MatchStats {
+    pub fn team_results(&self) -> Vec<TeamResult> {
+        self.teams.iter().map(TeamStats::result).collect()
+    }
+}
+
+impl RoomStats {
+    /// The team results of every map of the series.
+    pub fn team_results(&self) -> Vec<Vec<TeamResult>> {
+        self.0.iter().map(MatchStats::team_results).collect()
+    }
+}
+
 impl MapStats for RoomStats {
     fn map_stats(&mut self, mapping: &crate::Mapping) {
         self.0.iter_mut().for_each(|stats| {
@@ -109,6 +190,8 @@ impl Into<MatchStats> for MatchStats_ {
 
         MatchStats {
             date,
+            best_of: self.best_of.parse().unwrap_or(1),
+            round: self.match_round.parse().unwrap_or(1),
             game: self.game,
             game_mode: self.game_mode,
             match_id: self.match_id,
@@ -129,3 +212,58 @@ impl Client {
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        let pairs = pairs.iter().map(|(k, v)| (k.to_string(), v.to_string()));
+        pairs.collect()
+    }
+
+    fn player(id: &str, pairs: &[(&str, &str)]) -> PlayerStats {
+        PlayerStats {
+            nickname: id.to_string(),
+            player_id: id.to_string(),
+            stats: stats(pairs),
+        }
+    }
+
+    #[test]
+    fn team_result() {
+        let a = player(
+            "a",
+            &[("Kills", "20"), ("Deaths", "10"), ("K/D Ratio", "2")],
+        );
+        let b = player(
+            "b",
+            &[("Kills", "10"), ("Deaths", "20"), ("Penta Kills", "1")],
+        );
+        let c = player("c", &[("Kills", "x"), ("K/D Ratio", "1")]);
+        let team = TeamStats {
+            team_id: "t1".to_string(),
+            premade: true,
+            players: vec![a, b, c],
+            stats: stats(&[
+                ("Team", "team_a"),
+                ("Team Win", "1"),
+                ("Final Score", "16"),
+                ("First Half Score", "9"),
+                ("Second Half Score", "7"),
+            ]),
+        };
+        let result = team.result();
+
+        assert_eq!(result.name.as_deref(), Some("team_a"));
+        assert!(result.premade);
+        assert_eq!(result.won, Some(true));
+        assert_eq!(result.final_score, Some(16));
+        assert_eq!(result.first_half_score, Some(9));
+        assert_eq!(result.second_half_score, Some(7));
+        assert_eq!(result.overtime_score, None);
+        // Unparsable and missing stats are skipped.
+        assert_eq!((result.kills, result.deaths, result.assists), (30, 30, 0));
+        assert_eq!(result.average_kd, Some(1.5));
+        assert_eq!((result.triple_kills, result.penta_kills), (0, 1));
+    }
+}
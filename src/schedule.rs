@@ -0,0 +1,135 @@
+use crate::matches::Matches;
+
+use chrono::{Datelike, TimeZone, Timelike, Weekday};
+use serde::Serialize;
+
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct Bucket {
+    pub matches: usize,
+    pub wins: usize,
+    pub kills: u32,
+    pub deaths: u32,
+    pub elo_change: i32,
+}
+
+impl Bucket {
+    pub fn win_rate(&self) -> Option<f32> {
+        (self.matches > 0).then(|| self.wins as f32 / self.matches as f32)
+    }
+    pub fn kd(&self) -> Option<f32> {
+        (self.deaths > 0).then(|| self.kills as f32 / self.deaths as f32)
+    }
+}
+
+/// Performance of a player by the time the matches were played.
+///
+/// Expects the stats of the matches to be mapped, see [`Matches::map_stats`].
+#[derive(Debug, Serialize)]
+pub struct Schedule {
+    /// 24 buckets, starting at midnight.
+    pub by_hour: Vec<Bucket>,
+    /// 7 buckets, starting on monday.
+    pub by_weekday: Vec<Bucket>,
+}
+
+fn best<'a, I>(buckets: I, min_matches: usize) -> Option<usize>
+where
+    I: Iterator<Item = &'a Bucket>,
+{
+    let rates = buckets
+        .enumerate()
+        .filter(|(_, b)| b.matches >= min_matches);
+    let rates = rates.filter_map(|(idx, b)| Some((idx, b.win_rate()?)));
+    let (idx, _) = rates.max_by(|a, b| a.1.total_cmp(&b.1))?;
+    Some(idx)
+}
+
+impl Schedule {
+    /// Buckets the matches by the hour and weekday in the given timezone,
+    /// e.g. [`chrono::Local`], [`chrono::Utc`] or a [`chrono::FixedOffset`].
+    pub fn from_matches<Tz: TimeZone>(matches: &Matches, tz: &Tz) -> Schedule {
+        let mut by_hour = vec![Bucket::default(); 24];
+        let mut by_weekday = vec![Bucket::default(); 7];
+
+        for (m, delta) in matches.0.iter().zip(matches.elo_deltas()) {
+            let date = m.date.with_timezone(tz);
+            let hour = date.hour() as usize;
+            let weekday = date.weekday().num_days_from_monday() as usize;
+
+            for bucket in [&mut by_hour[hour], &mut by_weekday[weekday]] {
+                bucket.matches += 1;
+                bucket.wins += usize::from(m.won() == Some(true));
+                bucket.kills += m.kills().unwrap_or_default();
+                bucket.deaths += m.deaths().unwrap_or_default();
+                bucket.elo_change += delta.unwrap_or_default();
+            }
+        }
+
+        Schedule {
+            by_hour,
+            by_weekday,
+        }
+    }
+
+    /// The hour with the highest win rate out of those with at least `min_matches`.
+    pub fn best_hour(&self, min_matches: usize) -> Option<u32> {
+        best(self.by_hour.iter(), min_matches).map(|hour| hour as u32)
+    }
+
+    /// The weekday with the highest win rate out of those with at least `min_matches`.
+    pub fn best_weekday(&self, min_matches: usize) -> Option<Weekday> {
+        let day = best(self.by_weekday.iter(), min_matches)?;
+        Some((0..day).fold(Weekday::Mon, |weekday, _| weekday.succ()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matches::Match;
+
+    use chrono::{FixedOffset, Local, Utc};
+
+    fn played(day: u32, hour: u32, won: bool, kills: u32, deaths: u32, elo: u16) -> Match {
+        let stats = [
+            ("Result", u32::from(won)),
+            ("Kills", kills),
+            ("Deaths", deaths),
+        ];
+        let date = Utc.with_ymd_and_hms(2022, 9, day, hour, 0, 0).unwrap();
+        Match::fixture("match", date.with_timezone(&Local), Some(elo), stats)
+    }
+
+    #[test]
+    fn buckets_by_hour_and_weekday() {
+        // Newest first, the 5th of september 2022 was a monday.
+        let matches = Matches(vec![
+            played(6, 20, true, 20, 10, 1050),
+            played(5, 20, true, 10, 10, 1025),
+            played(5, 9, false, 5, 15, 1000),
+            played(4, 20, false, 0, 10, 1025),
+        ]);
+        let schedule = Schedule::from_matches(&matches, &Utc);
+
+        let evening = &schedule.by_hour[20];
+        assert_eq!((evening.matches, evening.wins), (3, 2));
+        assert_eq!(evening.kd(), Some(1.0));
+        assert_eq!(evening.elo_change, 50);
+        assert_eq!(schedule.by_hour[9].elo_change, -25);
+        assert_eq!(schedule.by_hour[0].win_rate(), None);
+
+        let monday = &schedule.by_weekday[0];
+        assert_eq!((monday.matches, monday.wins), (2, 1));
+        assert_eq!(schedule.by_weekday[6].matches, 1);
+
+        assert_eq!(schedule.best_hour(1), Some(20));
+        assert_eq!(schedule.best_hour(4), None);
+        assert_eq!(schedule.best_weekday(1), Some(Weekday::Tue));
+        assert_eq!(schedule.best_weekday(2), Some(Weekday::Mon));
+
+        let offset = FixedOffset::east_opt(10 * 60 * 60).unwrap();
+        let schedule = Schedule::from_matches(&matches, &offset);
+        assert_eq!(schedule.by_hour[6].matches, 3);
+        assert_eq!(schedule.by_weekday[2].matches, 1);
+    }
+}
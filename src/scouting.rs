@@ -0,0 +1,240 @@
+use crate::client::Client;
+use crate::map_performance::{MapPerformance, Window};
+use crate::mapping::{MapStats, Mapping};
+use crate::matches::Matches;
+use crate::player_info::PlayerInfo;
+use crate::room::{Player, Room, Team};
+use crate::shared::mean;
+use crate::stats::Stats;
+
+use std::collections::{BTreeSet, HashMap};
+
+use chrono::Local;
+use serde::Serialize;
+
+/// How many of the most played maps are reported as preferred.
+const PREFERRED_MAPS: usize = 3;
+
+#[derive(Debug, Serialize)]
+pub struct PlayerReport {
+    pub id: String,
+    pub nickname: String,
+    pub elo: u16,
+    pub skill_level: u8,
+    pub party_id: Option<String>,
+    pub party_size: usize,
+    pub account_age_days: i64,
+    pub lifetime_matches: u16,
+    pub lifetime_kd: Option<f32>,
+    /// Between `0.0` and `1.0`.
+    pub lifetime_win_rate: Option<f32>,
+    pub recent_matches: usize,
+    pub recent_wins: usize,
+    pub recent_kd: Option<f32>,
+    pub preferred_maps: Vec<String>,
+    pub avoided_maps: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TeamReport {
+    pub id: String,
+    pub name: String,
+    pub leader: String,
+    pub average_elo: f32,
+    pub average_recent_kd: Option<f32>,
+    /// Player ids of every party with more than one member.
+    pub premades: Vec<Vec<String>>,
+    pub players: Vec<PlayerReport>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ScoutingReport {
+    pub room_id: String,
+    pub map_pool: Vec<String>,
+    pub faction_1: TeamReport,
+    pub faction_2: TeamReport,
+}
+
+struct Scouted {
+    info: PlayerInfo,
+    stats: Stats,
+    matches: Matches,
+}
+
+fn recent_kd(matches: &Matches) -> Option<f32> {
+    let kills: u32 = matches.0.iter().filter_map(|m| m.kills()).sum();
+    let deaths: u32 = matches.0.iter().filter_map(|m| m.deaths()).sum();
+    match (kills, deaths) {
+        (0, 0) => None,
+        (kills, 0) => Some(kills as f32),
+        (kills, deaths) => Some(kills as f32 / deaths as f32),
+    }
+}
+
+fn player_report(
+    player: &Player,
+    scouted: &Scouted,
+    room: &Room,
+    map_pool: &BTreeSet<String>,
+) -> PlayerReport {
+    let maps = MapPerformance::from_matches(&scouted.matches, Window::All);
+
+    let preferred_maps = maps.0.iter().take(PREFERRED_MAPS);
+    let preferred_maps = preferred_maps.map(|record| record.map.clone()).collect();
+    let avoided_maps = map_pool
+        .iter()
+        .filter(|&map| maps.get(map).is_none())
+        .cloned()
+        .collect();
+
+    let party_size = player
+        .party_id
+        .as_ref()
+        .and_then(|id| room.parties.get(id))
+        .map(|members| members.len())
+        .unwrap_or(1);
+    let recent_wins = scouted.matches.0.iter();
+    let recent_wins = recent_wins.filter(|m| m.won() == Some(true)).count();
+
+    PlayerReport {
+        id: player.id.clone(),
+        nickname: player.nickname.clone(),
+        elo: player.elo,
+        skill_level: player.skill_level,
+        party_id: player.party_id.clone(),
+        party_size,
+        account_age_days: scouted.info.account_age(&Local::now()).num_days(),
+        lifetime_matches: scouted.stats.matches,
+        lifetime_kd: scouted.stats.kd(),
+        lifetime_win_rate: scouted.stats.win_rate().map(|rate| rate / 100.0),
+        recent_matches: scouted.matches.0.len(),
+        recent_wins,
+        recent_kd: recent_kd(&scouted.matches),
+        preferred_maps,
+        avoided_maps,
+    }
+}
+
+fn team_report(
+    team: &Team,
+    scouted: &HashMap<String, Scouted>,
+    room: &Room,
+    map_pool: &BTreeSet<String>,
+) -> TeamReport {
+    let players = team
+        .roster
+        .iter()
+        .map(|player| player_report(player, &scouted[&player.id], room, map_pool))
+        .collect::<Vec<_>>();
+
+    let average_recent_kd = mean(players.iter().filter_map(|p| p.recent_kd));
+
+    let roster = team
+        .roster
+        .iter()
+        .map(|p| p.id.as_str())
+        .collect::<Vec<_>>();
+    let mut premades = room
+        .parties
+        .values()
+        .filter(|members| members.len() > 1)
+        .filter(|members| members.iter().all(|id| roster.contains(&id.as_str())))
+        .cloned()
+        .collect::<Vec<_>>();
+    premades.sort();
+
+    TeamReport {
+        id: team.id.clone(),
+        name: team.name.clone(),
+        leader: team.leader.clone(),
+        average_elo: team.average_elo(),
+        average_recent_kd,
+        premades,
+        players,
+    }
+}
+
+impl Client {
+    async fn scout_player(
+        &self,
+        player: &Player,
+        mapping: &Mapping,
+        recent: usize,
+    ) -> reqwest::Result<Scouted> {
+        let (info, mut stats, mut matches) = futures::try_join!(
+            self.info(&player.nickname),
+            self.stats(&player.id),
+            self.matches(&player.id, recent, 0),
+        )?;
+        stats.map_stats(mapping);
+        matches.map_stats(mapping);
+
+        Ok(Scouted {
+            info,
+            stats,
+            matches,
+        })
+    }
+
+    /// Fetches the lifetime stats, the last `recent` matches and the profile
+    /// of every player in the room and summarizes them per player and team.
+    ///
+    /// The map pool is taken from the veto if there is one, otherwise it
+    /// consists of every map any player in the room played recently.
+    pub async fn scout(
+        &self,
+        room_id: &str,
+        mapping: &Mapping,
+        recent: usize,
+    ) -> reqwest::Result<ScoutingReport> {
+        let room = self.room(room_id).await?;
+
+        let mut scouted = HashMap::new();
+        for player in room.players() {
+            let player_data = self.scout_player(player, mapping, recent).await?;
+            scouted.insert(player.id.clone(), player_data);
+        }
+
+        let mut map_pool = match self.veto(room_id).await {
+            Ok(veto) => veto.map_pool().map(String::from).collect(),
+            Err(_) => BTreeSet::new(),
+        };
+        if map_pool.is_empty() {
+            let played = scouted.values().flat_map(|s| s.matches.0.iter());
+            map_pool = played.filter_map(|m| m.map()).map(String::from).collect();
+        }
+
+        let faction_1 = team_report(&room.teams.faction_1, &scouted, &room, &map_pool);
+        let faction_2 = team_report(&room.teams.faction_2, &scouted, &room, &map_pool);
+
+        Ok(ScoutingReport {
+            room_id: room.id,
+            map_pool: map_pool.into_iter().collect(),
+            faction_1,
+            faction_2,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matches::Match;
+    use crate::shared::unix_time_sec;
+
+    fn played(kills: u32, deaths: u32) -> Match {
+        let stats = [("Kills", kills), ("Deaths", deaths)];
+        Match::fixture("match", unix_time_sec(0), None, stats)
+    }
+
+    #[test]
+    fn recent_kd_over_every_match() {
+        // Summed over every match, not averaged per match.
+        let matches = Matches(vec![played(30, 10), played(10, 30)]);
+        assert_eq!(recent_kd(&matches), Some(1.0));
+
+        assert_eq!(recent_kd(&Matches(vec![played(12, 0)])), Some(12.0));
+        assert_eq!(recent_kd(&Matches(vec![played(0, 0)])), None);
+        assert_eq!(recent_kd(&Matches(Vec::new())), None);
+    }
+}
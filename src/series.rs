@@ -0,0 +1,176 @@
+use crate::client::Client;
+use crate::mapping::{MapStats, Mapping};
+use crate::room::Room;
+use crate::room_stats::{PlayerStats, RoomStats, TeamResult};
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+pub struct SeriesMap {
+    /// Which map of the series this is, starting at `1`.
+    pub round: u8,
+    pub map: Option<String>,
+    /// Score as reported by the api, e.g. `16 / 12`.
+    pub score: Option<String>,
+    /// Team id of the winner.
+    pub winner: Option<String>,
+    pub teams: Vec<TeamResult>,
+    pub players: Vec<PlayerStats>,
+}
+
+/// A BO1, BO3 or BO5 with the results of every played map.
+///
+/// Expects the room stats to be mapped, see [`crate::MapStats`].
+#[derive(Debug, Serialize)]
+pub struct Series {
+    pub room_id: String,
+    pub best_of: u8,
+    /// The maps picked during the veto, in the order they are played.
+    pub picked_maps: Vec<String>,
+    /// Every played map, ordered by round.
+    pub maps: Vec<SeriesMap>,
+    /// Maps won by each team, keyed by team id.
+    pub maps_won: HashMap<String, u8>,
+    /// Team id of the winner of the series.
+    pub winner: Option<String>,
+}
+
+impl Series {
+    pub fn new(room: &Room, stats: RoomStats) -> Series {
+        let best_of = stats.0.iter().map(|m| m.best_of).max();
+        let best_of = best_of.unwrap_or(room.maps.len().max(1) as u8);
+
+        let mut maps = stats
+            .0
+            .into_iter()
+            .map(|m| {
+                let teams = m.team_results();
+                let players = m.teams.into_iter().flat_map(|team| team.players);
+                SeriesMap {
+                    round: m.round,
+                    map: m.stats.get("Map").cloned(),
+                    score: m.stats.get("Score").cloned(),
+                    winner: m.stats.get("Winner").cloned(),
+                    teams,
+                    players: players.collect(),
+                }
+            })
+            .collect::<Vec<_>>();
+        maps.sort_by_key(|m| m.round);
+
+        let mut maps_won = HashMap::<String, u8>::new();
+        for winner in maps.iter().filter_map(|m| m.winner.as_ref()) {
+            *maps_won.entry(winner.clone()).or_default() += 1;
+        }
+
+        let winner = match room.winner() {
+            Some("faction1") => Some(room.teams.faction_1.id.clone()),
+            Some("faction2") => Some(room.teams.faction_2.id.clone()),
+            _ => maps_won
+                .iter()
+                .find(|(_, &won)| won > best_of / 2)
+                .map(|(team, _)| team.clone()),
+        };
+
+        Series {
+            room_id: room.id.clone(),
+            best_of,
+            picked_maps: room.maps.clone(),
+            maps,
+            maps_won,
+            winner,
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.winner.is_some()
+    }
+}
+
+impl Client {
+    pub async fn series(&self, room_id: &str, mapping: &Mapping) -> reqwest::Result<Series> {
+        let (room, mut stats) = futures::try_join!(self.room(room_id), self.room_stats(room_id))?;
+        stats.map_stats(mapping);
+        Ok(Series::new(&room, stats))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::room::MatchResult;
+    use crate::room_stats::{MatchStats, TeamStats};
+    use crate::shared::unix_time_sec;
+
+    fn team(team_id: &str, won: bool) -> TeamStats {
+        let player = PlayerStats {
+            nickname: format!("player {}", team_id),
+            player_id: format!("player-{}", team_id),
+            stats: HashMap::new(),
+        };
+        TeamStats {
+            team_id: team_id.to_string(),
+            premade: false,
+            players: vec![player],
+            stats: HashMap::from([("Team Win".to_string(), u8::from(won).to_string())]),
+        }
+    }
+
+    fn map(round: u8, map: &str, winner: &str) -> MatchStats {
+        let stats = [("Map", map), ("Score", "16 / 12"), ("Winner", winner)];
+        let stats = stats.iter().map(|(k, v)| (k.to_string(), v.to_string()));
+        MatchStats {
+            date: unix_time_sec(0),
+            best_of: 3,
+            round,
+            game: "csgo".to_string(),
+            game_mode: "5v5".to_string(),
+            match_id: "room".to_string(),
+            played: "1".to_string(),
+            teams: vec![team("1", winner == "1"), team("2", winner == "2")],
+            stats: stats.collect(),
+        }
+    }
+
+    fn room() -> Room {
+        let mut room = Room::fixture("room", &["a"], &["b"]);
+        room.maps = ["de_dust2", "de_mirage", "de_inferno"]
+            .map(String::from)
+            .to_vec();
+        room
+    }
+
+    #[test]
+    fn orders_maps_and_finds_the_winner() {
+        let stats = RoomStats(vec![map(2, "de_mirage", "1"), map(1, "de_dust2", "1")]);
+        let series = Series::new(&room(), stats);
+
+        assert_eq!(series.best_of, 3);
+        assert_eq!(series.picked_maps.len(), 3);
+        let rounds = series.maps.iter().map(|m| (m.round, m.map.as_deref()));
+        let rounds = rounds.collect::<Vec<_>>();
+        assert_eq!(rounds, [(1, Some("de_dust2")), (2, Some("de_mirage"))]);
+        assert_eq!(series.maps[0].teams[0].won, Some(true));
+        assert_eq!(series.maps[0].players.len(), 2);
+        assert_eq!(series.maps_won["1"], 2);
+        assert_eq!(series.winner.as_deref(), Some("1"));
+
+        // A single map of a BO3 doesn't decide the series.
+        let series = Series::new(&room(), RoomStats(vec![map(1, "de_dust2", "2")]));
+        assert!(!series.is_finished());
+
+        // The result of the room takes precedence.
+        let mut room = room();
+        room.results.push(MatchResult {
+            winner: "faction2".to_string(),
+            score: HashMap::new(),
+        });
+        let series = Series::new(&room, RoomStats(vec![map(1, "de_dust2", "1")]));
+        assert_eq!(series.winner.as_deref(), Some("2"));
+
+        // Without stats the series is as long as the veto picked maps.
+        assert_eq!(Series::new(&room, RoomStats(Vec::new())).best_of, 3);
+    }
+}
@@ -0,0 +1,147 @@
+use crate::room::Room;
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+#[derive(Debug, Default, Serialize)]
+pub struct LocationRecord {
+    pub matches: usize,
+    pub wins: usize,
+}
+
+impl LocationRecord {
+    pub fn win_rate(&self) -> Option<f32> {
+        (self.matches > 0).then(|| self.wins as f32 / self.matches as f32)
+    }
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct PlayerLocations {
+    pub matches: usize,
+    /// Keyed by the voted location, or the server country if there was no vote.
+    pub locations: HashMap<String, LocationRecord>,
+    /// Matches where the country of the player is known.
+    pub country_known: usize,
+    /// Matches on a server in a different country than the player's.
+    pub foreign_server: usize,
+}
+
+impl PlayerLocations {
+    /// Share of matches on a server outside of the player's country.
+    pub fn foreign_server_rate(&self) -> Option<f32> {
+        let known = self.country_known;
+        (known > 0).then(|| self.foreign_server as f32 / known as f32)
+    }
+}
+
+/// Server locations played across many rooms.
+#[derive(Debug, Default, Serialize)]
+pub struct ServerStats {
+    /// How often each location was played.
+    pub locations: HashMap<String, usize>,
+    /// Locations played per organizer (hub, queue, ...) id.
+    pub organizers: HashMap<String, HashMap<String, usize>>,
+    /// Keyed by player id.
+    pub players: HashMap<String, PlayerLocations>,
+}
+
+fn location(room: &Room) -> Option<&str> {
+    let server = room.server.as_ref().map(|s| s.country.as_str());
+    room.location.as_deref().or(server)
+}
+
+impl ServerStats {
+    /// `countries` maps player ids to their country code, see [`crate::PlayerInfo`].
+    /// Players without a country are not taken into account for `foreign_server`.
+    pub fn from_rooms<'a, I>(rooms: I, countries: &HashMap<String, String>) -> ServerStats
+    where
+        I: IntoIterator<Item = &'a Room>,
+    {
+        let mut stats = ServerStats::default();
+        for room in rooms {
+            stats.add(room, countries);
+        }
+        stats
+    }
+
+    pub fn add(&mut self, room: &Room, countries: &HashMap<String, String>) {
+        let location = match location(room) {
+            Some(location) => location,
+            None => return,
+        };
+        *self.locations.entry(location.to_string()).or_default() += 1;
+        let organizer = self.organizers.entry(room.organizer_id.clone());
+        *organizer
+            .or_default()
+            .entry(location.to_string())
+            .or_default() += 1;
+
+        let winner = room.winner();
+        let server_country = room.server.as_ref().map(|s| s.country.as_str());
+        for player in room.players() {
+            let won = winner.is_some() && winner == room.faction_of(&player.id);
+            let locations = self.players.entry(player.id.clone()).or_default();
+            locations.matches += 1;
+
+            let record = locations.locations.entry(location.to_string());
+            let record = record.or_default();
+            record.matches += 1;
+            record.wins += usize::from(won);
+
+            if let (Some(server), Some(country)) = (server_country, countries.get(&player.id)) {
+                locations.country_known += 1;
+                let foreign = !server.eq_ignore_ascii_case(country);
+                locations.foreign_server += usize::from(foreign);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::room::{MatchResult, Server};
+
+    fn room(id: &str, location: Option<&str>, country: &str, winner: &str) -> Room {
+        let mut room = Room::fixture(id, &["a", "b"], &["c", "d"]);
+        room.location = location.map(str::to_string);
+        room.server = Some(Server {
+            country: country.to_string(),
+            ip: "127.0.0.1".to_string(),
+            port: "27015".to_string(),
+        });
+        room.results.push(MatchResult {
+            winner: winner.to_string(),
+            score: HashMap::new(),
+        });
+        room
+    }
+
+    #[test]
+    fn counts_locations_and_foreign_servers() {
+        let rooms = [
+            room("r1", Some("Frankfurt"), "DE", "faction1"),
+            room("r2", Some("Frankfurt"), "DE", "faction2"),
+            // Without a vote the server country is the location.
+            room("r3", None, "SE", "faction1"),
+        ];
+        let countries = [("a", "de"), ("c", "se")];
+        let countries = countries
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()));
+        let stats = ServerStats::from_rooms(&rooms, &countries.collect());
+
+        assert_eq!(stats.locations["Frankfurt"], 2);
+        assert_eq!(stats.locations["SE"], 1);
+        assert_eq!(stats.organizers["faceit"]["Frankfurt"], 2);
+
+        let a = &stats.players["a"];
+        assert_eq!(a.matches, 3);
+        assert_eq!(a.locations["Frankfurt"].win_rate(), Some(0.5));
+        assert_eq!(a.locations["SE"].wins, 1);
+        assert_eq!(a.foreign_server_rate(), Some(1.0 / 3.0));
+        assert_eq!(stats.players["c"].foreign_server_rate(), Some(2.0 / 3.0));
+        assert_eq!(stats.players["b"].foreign_server_rate(), None);
+    }
+}
@@ -0,0 +1,138 @@
+use crate::client::Client;
+
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use reqwest::cookie::{CookieStore, Jar};
+use reqwest::header::{self, HeaderMap, HeaderValue};
+
+const DOMAIN: &str = "https://www.faceit.com";
+
+enum Credential {
+    Token(String),
+    Cookie { name: String, value: String },
+}
+
+/// The login of a faceit account, needed for endpoints that are only
+/// available to the account itself or to hub staff.
+///
+/// The credential can't be read back and is redacted from [`Debug`].
+pub struct Session(Credential);
+
+impl Session {
+    /// Sent as `Authorization: Bearer {TOKEN}` to every endpoint.
+    pub fn token(token: impl Into<String>) -> Session {
+        Session(Credential::Token(token.into()))
+    }
+    /// Sent as a cookie to every faceit subdomain.
+    pub fn cookie(name: impl Into<String>, value: impl Into<String>) -> Session {
+        Session(Credential::Cookie {
+            name: name.into(),
+            value: value.into(),
+        })
+    }
+}
+
+impl Debug for Session {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let kind = match self.0 {
+            Credential::Token(_) => "token",
+            Credential::Cookie { .. } => "cookie",
+        };
+        f.debug_tuple("Session")
+            .field(&kind)
+            .field(&"<redacted>")
+            .finish()
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum SessionError {
+    #[error("the credential contains characters that are not allowed in a header")]
+    InvalidCredential,
+    #[error("failed to build the client: {0}")]
+    Client(#[from] reqwest::Error),
+}
+
+/// A token as defined by RFC 6265, without separators like `=` or `;`.
+fn is_cookie_name(name: &str) -> bool {
+    const SEPARATORS: &[u8] = b"()<>@,;:\\\"/[]?={} \t";
+    let valid = |b: &u8| b.is_ascii_graphic() && !SEPARATORS.contains(b);
+    !name.is_empty() && name.as_bytes().iter().all(valid)
+}
+
+/// Printable ascii without whitespace, `"`, `,`, `;` and `\`.
+fn is_cookie_value(value: &str) -> bool {
+    let valid = |b: &u8| b.is_ascii_graphic() && !b"\",;\\".contains(b);
+    value.as_bytes().iter().all(valid)
+}
+
+impl Client {
+    /// A client that sends the session with every request.
+    pub fn with_session(session: Session) -> Result<Client, SessionError> {
+        let builder = reqwest::Client::builder();
+        let builder = match session.0 {
+            Credential::Token(token) => {
+                let value = format!("Bearer {}", token);
+                let mut value =
+                    HeaderValue::from_str(&value).map_err(|_| SessionError::InvalidCredential)?;
+                // Keeps the value out of the `Debug` output of the client.
+                value.set_sensitive(true);
+
+                let mut headers = HeaderMap::new();
+                headers.insert(header::AUTHORIZATION, value);
+                builder.default_headers(headers)
+            }
+            Credential::Cookie { name, value } => {
+                if !is_cookie_name(&name) || !is_cookie_value(&value) {
+                    return Err(SessionError::InvalidCredential);
+                }
+                let cookie = format!("{}={}; Domain=faceit.com; Secure; HttpOnly", name, value);
+                let url = DOMAIN.parse().unwrap();
+                let jar = Jar::default();
+                jar.add_cookie_str(&cookie, &url);
+                // The jar silently drops cookies it can't parse.
+                if jar.cookies(&url).is_none() {
+                    return Err(SessionError::InvalidCredential);
+                }
+                builder.cookie_provider(Arc::new(jar))
+            }
+        };
+
+        Ok(Client {
+            client: builder.build()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redacts_credential() {
+        let session = Session::token("secret-token");
+        assert!(!format!("{:?}", session).contains("secret-token"));
+
+        let client = Client::with_session(session).unwrap();
+        assert!(!format!("{:?}", client.client).contains("secret-token"));
+
+        let session = Session::cookie("t", "secret-cookie");
+        assert!(!format!("{:?}", session).contains("secret-cookie"));
+        assert!(Client::with_session(session).is_ok());
+
+        assert!(Client::with_session(Session::token("new\nline")).is_err());
+        let invalid = [
+            ("t", "value; Domain=evil.com"),
+            ("t=", "value"),
+            ("", "value"),
+        ];
+        for (name, value) in invalid {
+            let session = Session::cookie(name, value);
+            assert!(matches!(
+                Client::with_session(session),
+                Err(SessionError::InvalidCredential)
+            ));
+        }
+    }
+}
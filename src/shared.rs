@@ -1,4 +1,19 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+
 use chrono::{DateTime, Local, TimeZone, Utc};
+use serde::Deserialize;
+
+/// The envelope most endpoints of the internal api wrap their data in.
+#[derive(Deserialize)]
+pub(crate) struct Response<T> {
+    pub payload: T,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct Items<T> {
+    pub items: Vec<T>,
+}
 
 pub(crate) fn unix_time_ms(unix: u64) -> DateTime<Local> {
     let secs = unix / 1_000;
@@ -11,3 +26,24 @@ pub(crate) fn unix_time_sec(unix: u64) -> DateTime<Local> {
 pub(crate) fn parse_rfc3339(str: &str) -> DateTime<Local> {
     DateTime::<Local>::from(DateTime::parse_from_rfc3339(str).unwrap())
 }
+/// `None` for missing, empty or invalid timestamps, e.g. of rooms that did not finish yet.
+pub(crate) fn parse_time(str: Option<&str>) -> Option<DateTime<Local>> {
+    let time = DateTime::parse_from_rfc3339(str?).ok()?;
+    Some(time.with_timezone(&Local))
+}
+pub(crate) fn parse_stat<T: FromStr>(stats: &HashMap<String, String>, label: &str) -> Option<T> {
+    stats.get(label)?.trim().parse().ok()
+}
+pub(crate) fn mean<I: IntoIterator<Item = f32>>(values: I) -> Option<f32> {
+    let values = values.into_iter();
+    let (sum, count) = values.fold((0.0, 0), |(sum, count), v| (sum + v, count + 1));
+    (count > 0).then(|| sum / count as f32)
+}
+/// Linearly interpolated percentile of sorted values, `p` between `0.0` and `1.0`.
+pub(crate) fn percentile(sorted: &[f32], p: f32) -> Option<f32> {
+    let last = sorted.len().checked_sub(1)?;
+    let rank = p.clamp(0.0, 1.0) * last as f32;
+    let (lower, upper) = (rank.floor() as usize, rank.ceil() as usize);
+    let fraction = rank - lower as f32;
+    Some(sorted[lower] + (sorted[upper] - sorted[lower]) * fraction)
+}
@@ -0,0 +1,397 @@
+use crate::elo_history::EloHistory;
+use crate::matches::Matches;
+use crate::player_info::PlayerInfo;
+use crate::shared::mean;
+use crate::stats::Stats;
+
+use chrono::{DateTime, Local};
+use serde::Serialize;
+
+/// A signal fires if the observed value crosses `limit`
+/// and then adds `weight` to the score.
+#[derive(Debug, Clone, Copy)]
+pub struct Threshold<T> {
+    pub limit: T,
+    pub weight: f32,
+}
+
+#[derive(Debug, Clone)]
+pub struct SmurfConfig {
+    /// Fires if the account is younger than this many days.
+    pub account_age_days: Threshold<i64>,
+    /// Weight added if the phone number is not verified.
+    pub phone_unverified: f32,
+    /// Fires if the account played fewer matches than this.
+    pub lifetime_matches: Threshold<u16>,
+    /// Fires if the average elo gained per recent match is above this.
+    pub elo_per_match: Threshold<f32>,
+    /// Fires if the recent K/D is above this.
+    pub recent_kd: Threshold<f32>,
+    /// Fires if the recent headshot percentage is above this.
+    pub recent_headshot_pct: Threshold<f32>,
+    /// Fires if the recent win rate is above this, between `0.0` and `1.0`.
+    pub recent_win_rate: Threshold<f32>,
+    /// Players at or above this elo are checked for being boosted.
+    pub high_elo: u16,
+    /// Fires if the recent K/D of a high elo player is below this.
+    pub low_kd_at_high_elo: Threshold<f32>,
+    /// Fires if more than this share of the recent elo was gained in premade
+    /// matches, between `0.0` and `1.0`.
+    pub premade_elo_share: Threshold<f32>,
+    /// Category scores at or above this flag the category.
+    pub suspicious_score: f32,
+}
+
+impl Default for SmurfConfig {
+    fn default() -> SmurfConfig {
+        SmurfConfig {
+            account_age_days: Threshold {
+                limit: 90,
+                weight: 2.0,
+            },
+            phone_unverified: 1.0,
+            lifetime_matches: Threshold {
+                limit: 100,
+                weight: 1.5,
+            },
+            elo_per_match: Threshold {
+                limit: 15.0,
+                weight: 2.0,
+            },
+            recent_kd: Threshold {
+                limit: 1.5,
+                weight: 1.5,
+            },
+            recent_headshot_pct: Threshold {
+                limit: 60.0,
+                weight: 1.0,
+            },
+            recent_win_rate: Threshold {
+                limit: 0.75,
+                weight: 1.0,
+            },
+            high_elo: 2000,
+            low_kd_at_high_elo: Threshold {
+                limit: 0.9,
+                weight: 2.0,
+            },
+            premade_elo_share: Threshold {
+                limit: 0.8,
+                weight: 1.0,
+            },
+            suspicious_score: 0.5,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum Category {
+    /// A strong player on a new or low elo account.
+    Smurf,
+    /// A weak player whose elo was gained by others.
+    Boosted,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Signal {
+    pub name: &'static str,
+    pub category: Category,
+    pub weight: f32,
+    pub detail: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SmurfReport {
+    /// Weight of the fired smurf signals divided by the weight of all smurf signals.
+    pub smurf_score: f32,
+    /// Weight of the fired boosted signals divided by the weight of all boosted signals.
+    pub boosted_score: f32,
+    /// The categories whose score is at or above [`SmurfConfig::suspicious_score`].
+    pub flagged: Vec<Category>,
+    pub signals: Vec<Signal>,
+}
+
+impl SmurfReport {
+    /// Scores a player, expects the stats of `stats` and `matches`
+    /// to be mapped, see [`crate::MapStats`].
+    pub fn assess(
+        info: &PlayerInfo,
+        stats: &Stats,
+        matches: &Matches,
+        config: &SmurfConfig,
+        now: &DateTime<Local>,
+    ) -> SmurfReport {
+        use Category::{Boosted, Smurf};
+
+        let mut signals = Vec::new();
+        let mut fire = |category, name, weight, detail| {
+            signals.push(Signal {
+                name,
+                category,
+                weight,
+                detail,
+            })
+        };
+
+        let age = info.account_age(now).num_days();
+        if age < config.account_age_days.limit {
+            let detail = format!("account is {} days old", age);
+            fire(Smurf, "account_age", config.account_age_days.weight, detail);
+        }
+        if !info.phone_verified {
+            let detail = "phone number is not verified".to_string();
+            fire(Smurf, "phone_unverified", config.phone_unverified, detail);
+        }
+        if stats.matches < config.lifetime_matches.limit {
+            let detail = format!("played {} matches", stats.matches);
+            fire(
+                Smurf,
+                "lifetime_matches",
+                config.lifetime_matches.weight,
+                detail,
+            );
+        }
+
+        let history = EloHistory::from_matches(&matches.0);
+        if let (Some(first), Some(last)) = (history.0.first(), history.0.last()) {
+            let gained = last.elo as f32 - first.elo as f32;
+            let per_match = gained / history.0.len().saturating_sub(1).max(1) as f32;
+            if per_match > config.elo_per_match.limit {
+                let detail = format!("gained {:.1} elo per match", per_match);
+                fire(Smurf, "elo_per_match", config.elo_per_match.weight, detail);
+            }
+        }
+
+        let kd = mean(matches.0.iter().filter_map(|m| m.kd()));
+        if let Some(kd) = kd {
+            if kd > config.recent_kd.limit {
+                let detail = format!("recent K/D of {:.2}", kd);
+                fire(Smurf, "recent_kd", config.recent_kd.weight, detail);
+            }
+        }
+        if let Some(hs) = mean(matches.0.iter().filter_map(|m| m.headshot_pct())) {
+            if hs > config.recent_headshot_pct.limit {
+                let detail = format!("recent headshot percentage of {:.0}%", hs);
+                fire(
+                    Smurf,
+                    "recent_headshot_pct",
+                    config.recent_headshot_pct.weight,
+                    detail,
+                );
+            }
+        }
+        let wins = matches.0.iter().filter_map(|m| m.won());
+        if let Some(win_rate) = mean(wins.map(|won| if won { 1.0 } else { 0.0 })) {
+            if win_rate > config.recent_win_rate.limit {
+                let detail = format!("recent win rate of {:.0}%", win_rate * 100.0);
+                fire(
+                    Smurf,
+                    "recent_win_rate",
+                    config.recent_win_rate.weight,
+                    detail,
+                );
+            }
+        }
+
+        if let (true, Some(kd)) = (info.elo >= config.high_elo, kd) {
+            if kd < config.low_kd_at_high_elo.limit {
+                let detail = format!("recent K/D of {:.2} at {} elo", kd, info.elo);
+                fire(
+                    Boosted,
+                    "low_kd_at_high_elo",
+                    config.low_kd_at_high_elo.weight,
+                    detail,
+                );
+            }
+        }
+
+        let gains = matches.0.iter().zip(matches.elo_deltas());
+        let gains = gains.filter_map(|(m, delta)| Some((m.premade?, delta.filter(|&d| d > 0)?)));
+        let (premade, total) = gains.fold((0, 0), |(premade, total), (is_premade, gain)| {
+            (premade + if is_premade { gain } else { 0 }, total + gain)
+        });
+        if total > 0 {
+            let share = premade as f32 / total as f32;
+            if share > config.premade_elo_share.limit {
+                let detail = format!("gained {:.0}% of the elo in premades", share * 100.0);
+                fire(
+                    Boosted,
+                    "premade_elo_share",
+                    config.premade_elo_share.weight,
+                    detail,
+                );
+            }
+        }
+
+        let score = |category, total: f32| {
+            let fired = signals.iter().filter(|s| s.category == category);
+            let fired = fired.map(|s| s.weight).sum::<f32>();
+            if total > 0.0 {
+                fired / total
+            } else {
+                0.0
+            }
+        };
+        let smurf_score = score(
+            Smurf,
+            config.account_age_days.weight
+                + config.phone_unverified
+                + config.lifetime_matches.weight
+                + config.elo_per_match.weight
+                + config.recent_kd.weight
+                + config.recent_headshot_pct.weight
+                + config.recent_win_rate.weight,
+        );
+        let boosted_score = score(
+            Boosted,
+            config.low_kd_at_high_elo.weight + config.premade_elo_share.weight,
+        );
+
+        let flagged = [(Smurf, smurf_score), (Boosted, boosted_score)];
+        let flagged = flagged
+            .into_iter()
+            .filter(|&(_, score)| score >= config.suspicious_score)
+            .map(|(category, _)| category);
+
+        SmurfReport {
+            smurf_score,
+            boosted_score,
+            flagged: flagged.collect(),
+            signals,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matches::Match;
+
+    use std::collections::HashMap;
+
+    use chrono::{Duration, TimeZone};
+
+    fn now() -> DateTime<Local> {
+        Local.with_ymd_and_hms(2022, 9, 1, 12, 0, 0).unwrap()
+    }
+
+    fn info(age_days: i64, phone_verified: bool, elo: u16) -> PlayerInfo {
+        PlayerInfo {
+            id: "p1".to_string(),
+            country: "de".to_string(),
+            region: "EU".to_string(),
+            avatar: None,
+            cover_image: None,
+            created_at: now() - Duration::days(age_days),
+            friends: Vec::new(),
+            gender: None,
+            matching_sound: None,
+            memberships: Vec::new(),
+            nickname: "player".to_string(),
+            phone_verified,
+            game_id: "steam".to_string(),
+            game_name: "player".to_string(),
+            elo,
+            skill_level: 10,
+        }
+    }
+
+    fn stats(matches: u16) -> Stats {
+        Stats {
+            matches,
+            created_at: None,
+            updated_at: now(),
+            stats: HashMap::new(),
+        }
+    }
+
+    /// Ten matches, newest first, each gaining `gain` elo up to `elo`.
+    fn matches(elo: u16, gain: u16, kd: f32, hs: f32, premade: bool) -> Matches {
+        let matches = (0..10u16).map(|idx| {
+            let stats = [
+                ("K/D Ratio", kd.to_string()),
+                ("Headshots %", hs.to_string()),
+                ("Result", "1".to_string()),
+            ];
+            let date = now() - Duration::days(idx as i64);
+            let elo = Some(elo - idx * gain);
+            Match {
+                premade: Some(premade),
+                ..Match::fixture(&format!("m{}", idx), date, elo, stats)
+            }
+        });
+        Matches(matches.collect())
+    }
+
+    fn fired(report: &SmurfReport) -> Vec<&str> {
+        report.signals.iter().map(|s| s.name).collect()
+    }
+
+    #[test]
+    fn flags_smurfs() {
+        let config = SmurfConfig::default();
+        let matches = matches(1500, 30, 2.0, 65.0, false);
+        let report = SmurfReport::assess(
+            &info(30, false, 1500),
+            &stats(50),
+            &matches,
+            &config,
+            &now(),
+        );
+
+        assert_eq!(
+            fired(&report),
+            [
+                "account_age",
+                "phone_unverified",
+                "lifetime_matches",
+                "elo_per_match",
+                "recent_kd",
+                "recent_headshot_pct",
+                "recent_win_rate",
+            ]
+        );
+        assert!(report.signals.iter().all(|s| s.category == Category::Smurf));
+        assert_eq!(report.smurf_score, 1.0);
+        assert_eq!(report.flagged, [Category::Smurf]);
+    }
+
+    #[test]
+    fn flags_boosted_accounts() {
+        let config = SmurfConfig::default();
+        let matches = matches(2500, 10, 0.7, 35.0, true);
+        let report = SmurfReport::assess(
+            &info(900, true, 2500),
+            &stats(800),
+            &matches,
+            &config,
+            &now(),
+        );
+
+        assert_eq!(
+            fired(&report),
+            ["recent_win_rate", "low_kd_at_high_elo", "premade_elo_share"]
+        );
+        assert_eq!(report.boosted_score, 1.0);
+        assert_eq!(report.flagged, [Category::Boosted]);
+    }
+
+    #[test]
+    fn flags_nothing_for_regular_players() {
+        let config = SmurfConfig::default();
+        let mut matches = matches(1800, 0, 1.1, 45.0, false);
+        for m in matches.0.iter_mut().step_by(2) {
+            m.stats.insert("Result".to_string(), "0".to_string());
+        }
+        let report = SmurfReport::assess(
+            &info(900, true, 1800),
+            &stats(800),
+            &matches,
+            &config,
+            &now(),
+        );
+
+        assert!(report.signals.is_empty());
+        assert!(report.flagged.is_empty());
+    }
+}
@@ -1,6 +1,8 @@
-use crate::{client::Client, shared::unix_time_ms};
+use crate::mapping::{MapStats, Mapping};
+use crate::{client::Client, shared::parse_stat, shared::unix_time_ms};
 
 use std::collections::HashMap;
+use std::str::FromStr;
 
 use chrono::{DateTime, Local};
 use serde::Deserialize;
@@ -29,6 +31,28 @@ pub struct Stats {
     pub stats: HashMap<String, String>,
 }
 
+/// The stat accessors expect the stats to be mapped, see [`MapStats`].
+impl Stats {
+    pub fn stat<T: FromStr>(&self, label: &str) -> Option<T> {
+        parse_stat(&self.stats, label)
+    }
+    pub fn kd(&self) -> Option<f32> {
+        self.stat("Average K/D Ratio")
+    }
+    pub fn headshot_pct(&self) -> Option<f32> {
+        self.stat("Average Headshots %")
+    }
+    pub fn win_rate(&self) -> Option<f32> {
+        self.stat("Win Rate %")
+    }
+}
+
+impl MapStats for Stats {
+    fn map_stats(&mut self, mapping: &Mapping) {
+        self.stats.map_stats(mapping)
+    }
+}
+
 impl Into<Stats> for Response {
     fn into(self) -> Stats {
         let stats = self.lifetime.stats.into_iter();
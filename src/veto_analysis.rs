@@ -0,0 +1,191 @@
+use crate::democracy::{Democracy, PickBan};
+use crate::room::{Room, Team};
+
+use std::collections::{BTreeMap, HashMap};
+
+use serde::Serialize;
+
+#[derive(Debug, Default, Serialize)]
+pub struct VetoTendency {
+    /// How many vetoes this captain or team took part in.
+    pub vetoes: usize,
+    /// Map of the earliest ban per veto.
+    pub first_bans: HashMap<String, usize>,
+    pub bans: HashMap<String, usize>,
+    pub picks: HashMap<String, usize>,
+    /// Bans and picks that were made randomly because the time ran out.
+    pub random: HashMap<String, usize>,
+}
+
+fn most_frequent(counts: &HashMap<String, usize>) -> Option<&str> {
+    let counts = counts.iter();
+    let (map, _) = counts.max_by(|a, b| a.1.cmp(b.1).then_with(|| b.0.cmp(a.0)))?;
+    Some(map)
+}
+
+impl VetoTendency {
+    fn add(&mut self, veto: &[&PickBan]) {
+        self.vetoes += 1;
+
+        let first_ban = veto.iter().filter(|pb| pb.status == "drop" && !pb.random);
+        if let Some(first_ban) = first_ban.min_by_key(|pb| pb.round) {
+            *self.first_bans.entry(first_ban.guid.clone()).or_default() += 1;
+        }
+        for pick_ban in veto {
+            let counts = match (pick_ban.random, pick_ban.status.as_str()) {
+                (true, _) => &mut self.random,
+                (false, "drop") => &mut self.bans,
+                (false, "pick") => &mut self.picks,
+                _ => continue,
+            };
+            *counts.entry(pick_ban.guid.clone()).or_default() += 1;
+        }
+    }
+
+    pub fn likely_first_ban(&self) -> Option<&str> {
+        most_frequent(&self.first_bans)
+    }
+    pub fn likely_pick(&self) -> Option<&str> {
+        most_frequent(&self.picks)
+    }
+}
+
+/// Tendencies while the map pool stayed the same.
+#[derive(Debug, Default, Serialize)]
+pub struct PoolTendencies {
+    pub vetoes: usize,
+    /// Keyed by the player id of the team leader.
+    pub captains: HashMap<String, VetoTendency>,
+    /// Keyed by the team name.
+    pub teams: HashMap<String, VetoTendency>,
+}
+
+/// Veto statistics grouped by the map pool, which is keyed by its sorted
+/// map names joined with `,`.
+#[derive(Debug, Default, Serialize)]
+#[serde(transparent)]
+pub struct VetoAnalysis(pub BTreeMap<String, PoolTendencies>);
+
+impl VetoAnalysis {
+    pub fn from_vetoes<'a, I>(vetoes: I) -> VetoAnalysis
+    where
+        I: IntoIterator<Item = (&'a Democracy, &'a Room)>,
+    {
+        let mut analysis = VetoAnalysis::default();
+        for (veto, room) in vetoes {
+            analysis.add(veto, room);
+        }
+        analysis
+    }
+
+    pub fn add(&mut self, veto: &Democracy, room: &Room) {
+        let pool = self.0.entry(Self::pool_key(veto.map_pool())).or_default();
+        pool.vetoes += 1;
+
+        let factions = [
+            ("faction1", &room.teams.faction_1),
+            ("faction2", &room.teams.faction_2),
+        ];
+        for (faction, team) in factions {
+            let Team { leader, name, .. } = team;
+            let selected = veto.map_veto.iter();
+            let selected = selected.filter(|pb| pb.selected_by == faction);
+            let selected = selected.collect::<Vec<_>>();
+
+            let captain = pool.captains.entry(leader.clone()).or_default();
+            captain.add(&selected);
+            pool.teams.entry(name.clone()).or_default().add(&selected);
+        }
+    }
+
+    pub fn pool_key<'a, I>(map_pool: I) -> String
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        let mut maps = map_pool.into_iter().collect::<Vec<_>>();
+        maps.sort_unstable();
+        maps.join(",")
+    }
+
+    /// The tendencies of a captain for the given map pool.
+    pub fn captain<'a, I>(&self, leader: &str, map_pool: I) -> Option<&VetoTendency>
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        self.0.get(&Self::pool_key(map_pool))?.captains.get(leader)
+    }
+
+    /// The tendencies of a team for the given map pool.
+    pub fn team<'a, I>(&self, name: &str, map_pool: I) -> Option<&VetoTendency>
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        self.0.get(&Self::pool_key(map_pool))?.teams.get(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn veto(match_id: &str, pick_bans: &[(&str, &str, bool, u8, &str)]) -> Democracy {
+        let map_veto = pick_bans
+            .iter()
+            .map(|&(map, status, random, round, by)| PickBan {
+                guid: map.to_string(),
+                status: status.to_string(),
+                random,
+                round,
+                selected_by: by.to_string(),
+            });
+        Democracy {
+            match_id: match_id.to_string(),
+            map_veto: map_veto.collect(),
+        }
+    }
+
+    #[test]
+    fn groups_by_pool_captain_and_team() {
+        let vetoes = [
+            veto(
+                "r1",
+                &[
+                    ("de_mirage", "drop", false, 1, "faction1"),
+                    ("de_inferno", "drop", true, 2, "faction2"),
+                    ("de_dust2", "pick", false, 3, "faction1"),
+                ],
+            ),
+            veto(
+                "r2",
+                &[
+                    ("de_inferno", "drop", false, 1, "faction1"),
+                    ("de_dust2", "drop", false, 2, "faction2"),
+                    ("de_mirage", "drop", false, 3, "faction1"),
+                ],
+            ),
+        ];
+        let rooms = [
+            Room::fixture("r1", &["a", "b"], &["c", "d"]),
+            Room::fixture("r2", &["a", "b"], &["c", "d"]),
+        ];
+        let analysis = VetoAnalysis::from_vetoes(vetoes.iter().zip(rooms.iter()));
+
+        let pool = ["de_mirage", "de_dust2", "de_inferno"];
+        assert_eq!(analysis.0["de_dust2,de_inferno,de_mirage"].vetoes, 2);
+
+        let a = analysis.captain("a", pool).unwrap();
+        assert_eq!(a.vetoes, 2);
+        assert_eq!(a.bans["de_mirage"], 2);
+        // Ties are broken by the map name.
+        assert_eq!(a.likely_first_ban(), Some("de_inferno"));
+        assert_eq!(a.likely_pick(), Some("de_dust2"));
+
+        // Random bans are no first bans.
+        let c = analysis.team("team_2", pool).unwrap();
+        assert_eq!(c.random["de_inferno"], 1);
+        assert_eq!(c.likely_first_ban(), Some("de_dust2"));
+        assert_eq!(c.likely_pick(), None);
+
+        assert!(analysis.captain("a", ["de_dust2"]).is_none());
+    }
+}
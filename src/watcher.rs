@@ -0,0 +1,352 @@
+use crate::client::Client;
+use crate::matches::{Match, Matches};
+use crate::rate_limit::limiter;
+use crate::room::Room;
+use crate::room_stats::RoomStats;
+
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::time::Duration;
+
+use futures::stream::{self, Stream, StreamExt};
+use tokio::time::Interval;
+
+/// How many matches are fetched per player and poll, more new matches
+/// than this between two polls are missed.
+const PAGE_SIZE: usize = 20;
+
+/// How often fetching the room of a match is tried before it is skipped.
+const MAX_ATTEMPTS: usize = 3;
+
+#[derive(Debug)]
+pub struct MatchFinished {
+    pub player_id: String,
+    /// The entry from the match history of the player, with unmapped stats.
+    pub history: Match,
+    pub elo_delta: Option<i32>,
+    pub room: Room,
+    pub room_stats: RoomStats,
+}
+
+/// [`MatchFinished`] with the room and its stats left generic for testing.
+struct Finished<R, S> {
+    player_id: String,
+    history: Match,
+    elo_delta: Option<i32>,
+    room: R,
+    room_stats: S,
+}
+
+struct State<FM, FR, FS, R, S, E> {
+    fetch_matches: FM,
+    fetch_room: FR,
+    fetch_room_stats: FS,
+    players: Vec<String>,
+    interval: Duration,
+    /// Every request waits for a tick.
+    limiter: Interval,
+    /// The newest reported match id per polled player, `None` if the
+    /// history was empty.
+    seen: HashMap<String, Option<String>>,
+    /// Failed attempts per match id.
+    failures: HashMap<String, usize>,
+    pending: VecDeque<Result<Finished<R, S>, E>>,
+    first_poll: bool,
+}
+
+impl<FM, FR, FS, MF, RF, SF, R, S, E> State<FM, FR, FS, R, S, E>
+where
+    FM: FnMut(String) -> MF,
+    FR: FnMut(String) -> RF,
+    FS: FnMut(String) -> SF,
+    MF: Future<Output = Result<Matches, E>>,
+    RF: Future<Output = Result<R, E>>,
+    SF: Future<Output = Result<S, E>>,
+{
+    async fn finished(
+        &mut self,
+        player_id: &str,
+        history: Match,
+        elo_delta: Option<i32>,
+    ) -> Result<Finished<R, S>, E> {
+        self.limiter.tick().await;
+        let room = (self.fetch_room)(history.match_id.clone()).await?;
+        self.limiter.tick().await;
+        let room_stats = (self.fetch_room_stats)(history.match_id.clone()).await?;
+
+        Ok(Finished {
+            player_id: player_id.to_string(),
+            history,
+            elo_delta,
+            room,
+            room_stats,
+        })
+    }
+
+    async fn poll_player(&mut self, player_id: &str) -> Result<(), E> {
+        self.limiter.tick().await;
+        let matches = (self.fetch_matches)(player_id.to_string()).await?;
+        let newest = matches.0.first().map(|m| m.match_id.clone());
+        let last_seen = match self.seen.get(player_id) {
+            Some(last_seen) => last_seen.clone(),
+            None => {
+                // Everything before the player was watched is not new.
+                self.seen.insert(player_id.to_string(), newest);
+                return Ok(());
+            }
+        };
+
+        let deltas = matches.elo_deltas();
+        let new = matches.0.into_iter().zip(deltas);
+        let new = new.take_while(|(m, _)| Some(&m.match_id) != last_seen.as_ref());
+        let new = new.collect::<Vec<_>>();
+
+        for (history, elo_delta) in new.into_iter().rev() {
+            let match_id = history.match_id.clone();
+            if history.status.eq_ignore_ascii_case("finished") {
+                match self.finished(player_id, history, elo_delta).await {
+                    Ok(finished) => {
+                        self.failures.remove(&match_id);
+                        self.pending.push_back(Ok(finished));
+                    }
+                    Err(err) => {
+                        let attempts = self.failures.entry(match_id.clone()).or_default();
+                        *attempts += 1;
+                        if *attempts < MAX_ATTEMPTS {
+                            // The match and every newer one are retried with the next poll.
+                            return Err(err);
+                        }
+                        // Skip the match so it doesn't block the newer ones.
+                        self.failures.remove(&match_id);
+                        self.pending.push_back(Err(err));
+                    }
+                }
+            }
+            self.seen.insert(player_id.to_string(), Some(match_id));
+        }
+        Ok(())
+    }
+
+    async fn poll(&mut self) {
+        if !self.first_poll {
+            tokio::time::sleep(self.interval).await;
+        }
+        self.first_poll = false;
+
+        for player_id in self.players.clone() {
+            if let Err(err) = self.poll_player(&player_id).await {
+                self.pending.push_back(Err(err));
+            }
+        }
+    }
+}
+
+fn watch<FM, FR, FS, MF, RF, SF, R, S, E>(
+    fetch_matches: FM,
+    fetch_room: FR,
+    fetch_room_stats: FS,
+    players: Vec<String>,
+    limiter: Interval,
+    interval: Duration,
+) -> impl Stream<Item = Result<Finished<R, S>, E>>
+where
+    FM: FnMut(String) -> MF,
+    FR: FnMut(String) -> RF,
+    FS: FnMut(String) -> SF,
+    MF: Future<Output = Result<Matches, E>>,
+    RF: Future<Output = Result<R, E>>,
+    SF: Future<Output = Result<S, E>>,
+{
+    let state = State {
+        fetch_matches,
+        fetch_room,
+        fetch_room_stats,
+        players,
+        interval,
+        limiter,
+        seen: HashMap::new(),
+        failures: HashMap::new(),
+        pending: VecDeque::new(),
+        first_poll: true,
+    };
+    stream::unfold(state, |mut state| async move {
+        loop {
+            if let Some(event) = state.pending.pop_front() {
+                return Some((event, state));
+            }
+            state.poll().await;
+        }
+    })
+}
+
+impl Client {
+    /// Polls the match history of every player every `interval` and yields
+    /// every match that finished since the watcher started, oldest first.
+    ///
+    /// Every request waits for the same limiter, so at most `per_sec` requests
+    /// are sent per second, clamped between 1 and 1000. That is one request per
+    /// player and poll, plus two for every new match.
+    ///
+    /// Errors are yielded as they occur, the watcher keeps polling afterwards
+    /// and retries matches it failed to fetch, up to three times before they
+    /// are skipped. The stream never ends.
+    pub fn watch(
+        &self,
+        players: Vec<String>,
+        per_sec: u64,
+        interval: Duration,
+    ) -> impl Stream<Item = reqwest::Result<MatchFinished>> + '_ {
+        let events = watch(
+            move |player_id| async move { self.matches(&player_id, PAGE_SIZE, 0).await },
+            move |room_id| async move { self.room(&room_id).await },
+            move |room_id| async move { self.room_stats(&room_id).await },
+            players,
+            limiter(per_sec),
+            interval,
+        );
+        events.map(|event| {
+            event.map(|event| MatchFinished {
+                player_id: event.player_id,
+                history: event.history,
+                elo_delta: event.elo_delta,
+                room: event.room,
+                room_stats: event.room_stats,
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::cell::{Cell, RefCell};
+
+    use chrono::Local;
+    use futures::future::ready;
+
+    fn history(ids: &[&str]) -> Matches {
+        let matches = ids.iter().enumerate().map(|(idx, id)| {
+            let elo = Some(1000 + 25 * (ids.len() - idx) as u16);
+            Match::fixture(id, Local::now(), elo, Vec::<(&str, &str)>::new())
+        });
+        Matches(matches.collect())
+    }
+
+    #[tokio::test]
+    async fn retries_failed_matches_at_the_limit() {
+        let polls = RefCell::new(vec![history(&["m2", "m1"]), history(&["m1"])]);
+        let failed = Cell::new(false);
+        let requests = Cell::new(0);
+
+        let fetch_matches = |_| {
+            requests.set(requests.get() + 1);
+            let polls = &mut *polls.borrow_mut();
+            ready(Ok::<_, &str>(
+                polls.pop().unwrap_or_else(|| history(&["m2", "m1"])),
+            ))
+        };
+        let fetch_room = |room_id: String| {
+            requests.set(requests.get() + 1);
+            // The first attempt to fetch the room fails.
+            ready(match failed.replace(true) {
+                false => Err("timeout"),
+                true => Ok(room_id),
+            })
+        };
+        let fetch_room_stats = |room_id: String| {
+            requests.set(requests.get() + 1);
+            ready(Ok(format!("{} stats", room_id)))
+        };
+
+        let start = tokio::time::Instant::now();
+        let limiter = limiter(20);
+        let interval = Duration::from_millis(1);
+        let players = vec!["player".to_string()];
+        let events = watch(
+            fetch_matches,
+            fetch_room,
+            fetch_room_stats,
+            players,
+            limiter,
+            interval,
+        );
+        let events = events.take(2).collect::<Vec<_>>().await;
+
+        assert!(matches!(events[0], Err("timeout")));
+        let finished = events[1].as_ref().unwrap();
+        assert_eq!(finished.history.match_id, "m2");
+        assert_eq!(finished.elo_delta, Some(25));
+        assert_eq!(finished.room_stats, "m2 stats");
+
+        // One poll to start, one that fails and one that succeeds,
+        // every request spaced by at least 50ms.
+        assert_eq!(requests.get(), 6);
+        assert!(start.elapsed() >= Duration::from_millis(250));
+    }
+
+    #[tokio::test]
+    async fn reports_matches_after_an_empty_history() {
+        let polls = RefCell::new(vec![history(&["m1"]), history(&[])]);
+        let fetch_matches = |_| {
+            let polls = &mut *polls.borrow_mut();
+            ready(Ok::<_, &str>(
+                polls.pop().unwrap_or_else(|| history(&["m1"])),
+            ))
+        };
+        let fetch_room = |room_id: String| ready(Ok(room_id));
+        let fetch_room_stats = |room_id: String| ready(Ok(room_id));
+
+        let players = vec!["player".to_string()];
+        let interval = Duration::from_millis(1);
+        let events = watch(
+            fetch_matches,
+            fetch_room,
+            fetch_room_stats,
+            players,
+            limiter(1_000),
+            interval,
+        );
+        let events = events.take(1).collect::<Vec<_>>().await;
+
+        let finished = events[0].as_ref().unwrap();
+        assert_eq!(finished.history.match_id, "m1");
+        assert_eq!(finished.elo_delta, None);
+    }
+
+    #[tokio::test]
+    async fn skips_matches_that_keep_failing() {
+        let polls = RefCell::new(vec![history(&["m0"])]);
+        let fetch_matches = |_| {
+            let polls = &mut *polls.borrow_mut();
+            let next = polls.pop().unwrap_or_else(|| history(&["m2", "m1", "m0"]));
+            ready(Ok::<_, String>(next))
+        };
+        let fetch_room = |room_id: String| {
+            ready(match room_id.as_str() {
+                "m1" => Err(format!("{} not found", room_id)),
+                _ => Ok(room_id),
+            })
+        };
+        let fetch_room_stats = |room_id: String| ready(Ok(room_id));
+
+        let players = vec!["player".to_string()];
+        let interval = Duration::from_millis(1);
+        let events = watch(
+            fetch_matches,
+            fetch_room,
+            fetch_room_stats,
+            players,
+            limiter(1_000),
+            interval,
+        );
+        let events = events.take(MAX_ATTEMPTS + 1).collect::<Vec<_>>().await;
+
+        let (failed, finished) = events.split_at(MAX_ATTEMPTS);
+        assert!(failed
+            .iter()
+            .all(|e| e.as_ref().is_err_and(|e| e == "m1 not found")));
+        let finished = finished[0].as_ref().unwrap();
+        assert_eq!(finished.history.match_id, "m2");
+    }
+}
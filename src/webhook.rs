@@ -0,0 +1,234 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventKind {
+    #[serde(rename = "match_object_created")]
+    Created,
+    #[serde(rename = "match_status_configuring")]
+    Configuring,
+    #[serde(rename = "match_status_ready")]
+    Ready,
+    #[serde(rename = "match_status_finished")]
+    Finished,
+    #[serde(rename = "match_status_cancelled")]
+    Cancelled,
+    /// Hub and other events that are not modeled (yet).
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Entity {
+    pub id: String,
+    pub name: String,
+    /// E.g. `hub`, `championship` or `matchmaking`.
+    #[serde(rename = "type")]
+    pub kind: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct WebhookPlayer {
+    pub id: String,
+    pub nickname: String,
+    pub avatar: Option<String>,
+    pub game_id: Option<String>,
+    pub game_name: Option<String>,
+    pub game_skill_level: Option<u8>,
+    pub membership: Option<String>,
+    #[serde(default)]
+    pub anticheat_required: bool,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct WebhookTeam {
+    pub id: String,
+    pub name: String,
+    pub avatar: Option<String>,
+    pub leader_id: Option<String>,
+    #[serde(default)]
+    pub roster: Vec<WebhookPlayer>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct MatchPayload {
+    /// The room id, see [`crate::Client::room`].
+    pub id: String,
+    pub organizer_id: Option<String>,
+    pub region: Option<String>,
+    pub game: Option<String>,
+    pub entity: Option<Entity>,
+    /// `faction1` first, `faction2` second.
+    #[serde(default)]
+    pub teams: Vec<WebhookTeam>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+impl MatchPayload {
+    /// The faction the player plays for, `faction1` or `faction2`,
+    /// like [`crate::Room::faction_of`].
+    pub fn faction_of(&self, player_id: &str) -> Option<&'static str> {
+        let idx = self
+            .teams
+            .iter()
+            .position(|team| team.roster.iter().any(|p| p.id == player_id))?;
+        ["faction1", "faction2"].get(idx).copied()
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct WebhookEvent {
+    pub transaction_id: String,
+    pub event: EventKind,
+    pub event_id: String,
+    pub third_party_id: Option<String>,
+    pub app_id: Option<String>,
+    pub timestamp: DateTime<Utc>,
+    #[serde(default)]
+    pub retry_count: u32,
+    pub version: u32,
+    pub payload: MatchPayload,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum WebhookError {
+    #[error("missing or wrong secret header")]
+    Unauthorized,
+    #[error("invalid payload: {0}")]
+    Payload(#[from] serde_json::Error),
+    #[error("the receiver was dropped")]
+    Closed,
+}
+
+impl WebhookError {
+    /// The http status code to respond with.
+    pub fn status(&self) -> u16 {
+        match self {
+            WebhookError::Unauthorized => 401,
+            WebhookError::Payload(_) => 400,
+            WebhookError::Closed => 503,
+        }
+    }
+}
+
+/// Verifies and parses webhook requests and forwards the events to a channel.
+///
+/// Independent of any http server, pass the headers and the body of the
+/// request to [`WebhookHandler::handle`] and respond with `200` or [`WebhookError::status`].
+pub struct WebhookHandler {
+    header: String,
+    secret: String,
+    sender: mpsc::Sender<WebhookEvent>,
+}
+
+impl std::fmt::Debug for WebhookHandler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WebhookHandler")
+            .field("header", &self.header)
+            .field("secret", &"<redacted>")
+            .finish()
+    }
+}
+
+/// Compares in constant time for inputs of the same length.
+fn secure_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+impl WebhookHandler {
+    /// `header` and `secret` are the security header configured for the webhook.
+    pub fn new(
+        header: impl Into<String>,
+        secret: impl Into<String>,
+        sender: mpsc::Sender<WebhookEvent>,
+    ) -> WebhookHandler {
+        WebhookHandler {
+            header: header.into(),
+            secret: secret.into(),
+            sender,
+        }
+    }
+
+    pub fn channel(
+        header: impl Into<String>,
+        secret: impl Into<String>,
+        buffer: usize,
+    ) -> (WebhookHandler, mpsc::Receiver<WebhookEvent>) {
+        let (sender, receiver) = mpsc::channel(buffer);
+        (WebhookHandler::new(header, secret, sender), receiver)
+    }
+
+    pub fn verify<'h, I>(&self, headers: I) -> bool
+    where
+        I: IntoIterator<Item = (&'h str, &'h [u8])>,
+    {
+        headers
+            .into_iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(&self.header))
+            .is_some_and(|(_, value)| secure_eq(value, self.secret.as_bytes()))
+    }
+
+    /// Waits for capacity in the channel if it is full.
+    pub async fn handle<'h, I>(&self, headers: I, body: &[u8]) -> Result<(), WebhookError>
+    where
+        I: IntoIterator<Item = (&'h str, &'h [u8])>,
+    {
+        if !self.verify(headers) {
+            return Err(WebhookError::Unauthorized);
+        }
+        let event = serde_json::from_slice::<WebhookEvent>(body)?;
+        self.sender
+            .send(event)
+            .await
+            .map_err(|_| WebhookError::Closed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BODY: &str = r#"{
+        "transaction_id": "c5b5d8b3-7c5c-4bd5-9d5b-8c0e9a0b6a1e",
+        "event": "match_status_finished",
+        "event_id": "2c6f7b3e-4d2e-4c4b-8f5a-1b7a3e6d9c0f",
+        "third_party_id": "hub-id",
+        "app_id": "app-id",
+        "timestamp": "2022-09-01T18:42:10Z",
+        "retry_count": 0,
+        "version": 1,
+        "payload": {
+            "id": "1-room-id",
+            "organizer_id": "hub-id",
+            "region": "EU",
+            "game": "csgo",
+            "entity": { "id": "hub-id", "name": "Hub", "type": "hub" },
+            "teams": [
+                { "id": "t1", "name": "team_a", "roster": [{ "id": "p1", "nickname": "a" }] },
+                { "id": "t2", "name": "team_b", "roster": [{ "id": "p2", "nickname": "b" }] }
+            ],
+            "started_at": "2022-09-01T18:00:00Z",
+            "finished_at": "2022-09-01T18:42:00Z"
+        }
+    }"#;
+
+    #[tokio::test]
+    async fn forwards_verified_events() {
+        let (handler, mut events) = WebhookHandler::channel("X-Secret", "hunter2", 1);
+
+        let wrong = [("x-secret", "hunter3".as_bytes())];
+        let result = handler.handle(wrong, BODY.as_bytes()).await;
+        assert_eq!(result.map_err(|err| err.status()), Err(401));
+
+        let right = [("x-secret", "hunter2".as_bytes())];
+        handler.handle(right, BODY.as_bytes()).await.unwrap();
+
+        let event = events.recv().await.unwrap();
+        assert_eq!(event.event, EventKind::Finished);
+        assert_eq!(event.payload.faction_of("p2"), Some("faction2"));
+        assert!(!format!("{:?}", handler).contains("hunter2"));
+    }
+}